@@ -1,4 +1,4 @@
-use std::fmt::{Formatter, Result as FmtResult};
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 use serde::{
 	de::{Error as DeError, Visitor},
@@ -46,6 +46,70 @@ impl Color {
 
 		Self(r, g, b)
 	}
+
+	// hue is in degrees, saturation and value are clamped to `0.0..=1.0`.
+	#[allow(clippy::many_single_char_names)]
+	#[must_use]
+	pub fn from_hsv(hue: f64, saturation: f64, value: f64) -> Self {
+		let hue = hue.rem_euclid(360.0);
+		let saturation = saturation.clamp(0.0, 1.0);
+		let value = value.clamp(0.0, 1.0);
+
+		let chroma = value * saturation;
+		let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+		let m = value - chroma;
+
+		let (r, g, b) = match hue {
+			h if h < 60.0 => (chroma, x, 0.0),
+			h if h < 120.0 => (x, chroma, 0.0),
+			h if h < 180.0 => (0.0, chroma, x),
+			h if h < 240.0 => (0.0, x, chroma),
+			h if h < 300.0 => (x, 0.0, chroma),
+			_ => (chroma, 0.0, x),
+		};
+
+		Self(to_channel(r + m), to_channel(g + m), to_channel(b + m))
+	}
+
+	// `max` is always exactly one of the channels, so comparing floats directly is fine here.
+	#[must_use]
+	#[allow(clippy::float_cmp, clippy::many_single_char_names)]
+	pub fn to_hsv(self) -> (f64, f64, f64) {
+		let [r, g, b] = [self.r(), self.g(), self.b()].map(|channel| f64::from(channel) / 255.0);
+		let max = r.max(g).max(b);
+		let delta = max - r.min(g).min(b);
+
+		let hue = if delta == 0.0 {
+			0.0
+		} else if max == r {
+			60.0 * ((g - b) / delta).rem_euclid(6.0)
+		} else if max == g {
+			60.0 * ((b - r) / delta + 2.0)
+		} else {
+			60.0 * ((r - g) / delta + 4.0)
+		};
+
+		let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+		(hue, saturation, max)
+	}
+
+	// weighted by how bright each channel looks, per ITU-R BT.601.
+	#[must_use]
+	pub fn grayscale(self) -> Self {
+		let luminance = 0.299_f64.mul_add(
+			f64::from(self.r()),
+			0.587_f64.mul_add(f64::from(self.g()), 0.114 * f64::from(self.b())),
+		);
+		let gray = to_channel(luminance / 255.0);
+
+		Self(gray, gray, gray)
+	}
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_channel(value: f64) -> u8 {
+	(value * 255.0).round().clamp(0.0, 255.0) as u8
 }
 
 impl Default for Color {
@@ -54,6 +118,31 @@ impl Default for Color {
 	}
 }
 
+// the lowercase hex form, such as `#7289da`.
+impl Display for Color {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "#{:02x}{:02x}{:02x}", self.r(), self.g(), self.b())
+	}
+}
+
+impl From<(u8, u8, u8)> for Color {
+	fn from((r, g, b): (u8, u8, u8)) -> Self {
+		Self::new(r, g, b)
+	}
+}
+
+impl From<u32> for Color {
+	fn from(decimal: u32) -> Self {
+		Self::from_decimal(decimal)
+	}
+}
+
+impl From<Color> for u32 {
+	fn from(color: Color) -> Self {
+		color.to_decimal()
+	}
+}
+
 impl Serialize for Color {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
@@ -109,3 +198,62 @@ impl<'de> Deserialize<'de> for Color {
 		deserializer.deserialize_u32(ColorVisitor)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::Color;
+
+	#[test]
+	fn test_conversions() {
+		let blurple = Color::new(0x72, 0x89, 0xda);
+
+		assert_eq!(Color::from((0x72, 0x89, 0xda)), blurple);
+		assert_eq!(Color::from(0x0072_89da), blurple);
+		assert_eq!(u32::from(blurple), 0x0072_89da);
+		assert_eq!(Color::from(u32::from(blurple)), blurple);
+	}
+
+	#[test]
+	fn test_display() {
+		assert_eq!(format!("{}", Color::new(0x72, 0x89, 0xda)), "#7289da");
+		assert_eq!(Color::new(0, 0, 0).to_string(), "#000000");
+		assert_eq!(format!("{:?}", Color::new(1, 2, 3)), "Color(1, 2, 3)");
+	}
+
+	#[test]
+	fn test_hsv() {
+		let (hue, saturation, value) = Color::new(255, 0, 0).to_hsv();
+
+		assert!(hue.abs() < f64::EPSILON);
+		assert!((saturation - 1.0).abs() < f64::EPSILON);
+		assert!((value - 1.0).abs() < f64::EPSILON);
+
+		assert_eq!(Color::from_hsv(120.0, 1.0, 1.0), Color::new(0, 255, 0));
+		assert_eq!(Color::from_hsv(480.0, 2.0, 2.0), Color::new(0, 255, 0));
+		assert_eq!(Color::from_hsv(0.0, 0.0, 0.0), Color::new(0, 0, 0));
+
+		for color in [
+			Color::new(0x72, 0x89, 0xda),
+			Color::new(132, 61, 164),
+			Color::new(218, 0, 78),
+			Color::new(10, 20, 30),
+		] {
+			let (hue, saturation, value) = color.to_hsv();
+
+			assert_eq!(Color::from_hsv(hue, saturation, value), color);
+		}
+	}
+
+	#[test]
+	fn test_grayscale() {
+		assert_eq!(
+			Color::new(128, 128, 128).grayscale(),
+			Color::new(128, 128, 128)
+		);
+		assert_eq!(
+			Color::new(255, 255, 255).grayscale(),
+			Color::new(255, 255, 255)
+		);
+		assert_eq!(Color::new(255, 0, 0).grayscale(), Color::new(76, 76, 76));
+	}
+}
@@ -1,32 +1,62 @@
 use std::{
+	future::Future,
 	mem,
 	sync::atomic::{AtomicBool, Ordering},
+	time::{Duration, Instant},
 };
 
 use starlight_macros::model;
-use tracing::instrument;
+use tokio::time::timeout;
+use tracing::{info_span, Instrument, Span};
+use twilight_cache_inmemory::ResourceType;
+use twilight_http::{
+	client::InteractionClient,
+	request::{Request, TryIntoRequest},
+};
 use twilight_model::{
 	application::{
-		callback::{Autocomplete, InteractionResponse},
+		callback::{Autocomplete, CallbackData, InteractionResponse},
 		command::Command,
-		interaction::{application_command::CommandData, ApplicationCommand, InteractionType},
+		interaction::{
+			application_command::CommandData, ApplicationCommand, InteractionType,
+			MessageComponentInteraction,
+		},
 	},
 	channel::Message,
+	gateway::payload::incoming::MessageCreate,
+	guild::Permissions,
+	id::{
+		marker::{ChannelMarker, GuildMarker, UserMarker},
+		Id,
+	},
 };
-use twilight_util::builder::command::CommandBuilder;
+use twilight_standby::Standby;
 
-use super::Helpers;
+use super::{parsing::CommandOptions, Helpers};
 use crate::{
 	prelude::*,
+	settings::{GuildSettings, Tables, UserSettings},
 	slashies::{
-		commands::{Crate, Ping, Tag},
-		DefineCommand, SlashCommand, SlashData,
+		commands::{Crate, Help, Inspect, Ping, Preferences, Settings, Stats, Tag},
+		validate_command, CommandScope, ComponentRegistry, Cooldowns, DefineCommand, Paginator,
+		ResponseError, SlashCommand, SlashData,
 	},
-	state::{Context, QuickAccess},
+	state::{telemetry, Context, QuickAccess},
+	utils::DefaultMessages,
 };
 
 static INITIALIZED: AtomicBool = AtomicBool::new(false);
 
+static COOLDOWNS: Cooldowns = Cooldowns::new();
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum WaitError {
+	#[error("no message was sent in time")]
+	Timeout,
+	#[error("stopped waiting for a message")]
+	Canceled,
+}
+
 #[derive(Debug, Clone, Copy)]
 #[must_use = "an InteractionsHelper does nothing if not used"]
 pub struct InteractionsHelper(Helpers);
@@ -40,77 +70,216 @@ impl InteractionsHelper {
 		if INITIALIZED.load(Ordering::SeqCst) {
 			return Ok(());
 		}
-		let context = self.context();
+		let guild_id = self.config().guild_id;
+
+		for (target, commands) in Self::registration_targets(guild_id, cfg!(debug_assertions)) {
+			self.sync_commands(target, &commands).await?;
+		}
 
-		if let Some(guild_id) = context.config().guild_id {
-			context
-				.interaction_client()
-				.set_guild_commands(guild_id, &Self::get_slashies())
+		INITIALIZED.store(true, Ordering::SeqCst);
+		Ok(())
+	}
+
+	async fn sync_commands(
+		self,
+		guild_id: Option<Id<GuildMarker>>,
+		commands: &[Command],
+	) -> Result<()> {
+		for command in commands {
+			validate_command(command).into_diagnostic()?;
+		}
+
+		let interaction_client = self.interaction_client();
+
+		let current = if let Some(guild_id) = guild_id {
+			let get_commands = interaction_client.guild_commands(guild_id);
+			model!(get_commands as list of Command).await
+		} else {
+			let get_commands = interaction_client.global_commands();
+			model!(get_commands as list of Command).await
+		}
+		.into_diagnostic()?;
+
+		if commands_match(&current, commands) {
+			event!(Level::INFO, ?guild_id, "slash commands are up to date");
+			return Ok(());
+		}
+
+		event!(Level::INFO, ?guild_id, "updating slash commands");
+
+		if let Some(guild_id) = guild_id {
+			interaction_client
+				.set_guild_commands(guild_id, commands)
 				.exec()
 				.await
 		} else {
-			context
-				.interaction_client()
-				.set_global_commands(&Self::get_slashies())
+			interaction_client
+				.set_global_commands(commands)
 				.exec()
 				.await
 		}
 		.into_diagnostic()?;
 
-		INITIALIZED.store(true, Ordering::SeqCst);
 		Ok(())
 	}
 
-	#[instrument(skip(self, command), fields(command.name = %command.data.name, command.guild_id))]
+	// removes the named commands, or every command if none are named, returning the names of
+	// the removed commands. a dry run only logs what would be removed.
+	pub async fn remove_commands(
+		self,
+		guild_id: Option<Id<GuildMarker>>,
+		names: &[String],
+		dry_run: bool,
+	) -> Result<Vec<String>> {
+		let interaction_client = self.interaction_client();
+
+		let current = if let Some(guild_id) = guild_id {
+			let get_commands = interaction_client.guild_commands(guild_id);
+			model!(get_commands as list of Command).await
+		} else {
+			let get_commands = interaction_client.global_commands();
+			model!(get_commands as list of Command).await
+		}
+		.into_diagnostic()?;
+
+		let (kept, removed) = split_removed(current, names);
+
+		for name in &removed {
+			event!(Level::INFO, ?guild_id, %name, dry_run, "removing slash command");
+		}
+
+		if dry_run || removed.is_empty() {
+			return Ok(removed);
+		}
+
+		if let Some(guild_id) = guild_id {
+			interaction_client
+				.set_guild_commands(guild_id, &kept)
+				.exec()
+				.await
+		} else {
+			interaction_client.set_global_commands(&kept).exec().await
+		}
+		.into_diagnostic()?;
+
+		Ok(removed)
+	}
+
 	pub async fn handle(self, command: ApplicationCommand) {
-		if let Some(slashie) = Self::match_command(command.data.name.as_str(), command.data.clone())
-		{
-			let data = SlashData::new(command.clone());
-			match command.kind {
-				InteractionType::ApplicationCommand => {
-					if let Err(e) = slashie.run(self, data).await {
-						event!(
-							Level::ERROR,
-							error = &*e.root_cause(),
-							"error running command"
-						);
-
-						let mut err_data = SlashData::new(command);
-
-						err_data
-							.message("an error occurred running the interaction".to_owned())
-							.ephemeral();
-
-						if self.raw_get(&err_data).await.is_err() {
-							self.respond(&mut err_data).await.unwrap();
-						} else {
-							self.update(&mut err_data).await.unwrap();
-						}
+		let user_id = command
+			.member
+			.as_ref()
+			.and_then(|member| member.user.as_ref())
+			.or_else(|| command.user.as_ref())
+			.map(|user| user.id);
+
+		let span = command_span(&command.data.name, command.guild_id, user_id);
+
+		self.handle_command(command).instrument(span).await;
+	}
+
+	async fn handle_command(self, command: ApplicationCommand) {
+		let slashie = match Self::match_command(command.data.name.as_str(), command.data.clone()) {
+			Some(Ok(slashie)) => slashie,
+			Some(Err(e)) if command.kind == InteractionType::ApplicationCommand => {
+				self.report_error(command, &e).await;
+				return;
+			}
+			Some(Err(e)) => {
+				event!(
+					Level::ERROR,
+					error = &*e.root_cause(),
+					"error parsing command"
+				);
+				return;
+			}
+			None => {
+				event!(Level::WARN, "received unregistered command");
+				return;
+			}
+		};
+
+		let mut data = SlashData::new(command.clone());
+		match command.kind {
+			InteractionType::ApplicationCommand => {
+				if let Some(settings) = self.guild_settings(&data).await {
+					if let Some(reason) = blocked(&settings, data.user_id()) {
+						self.deny(data, reason).await;
+						return;
 					}
+
+					data.guild_locale = settings.locale().map(ToOwned::to_owned);
 				}
-				InteractionType::ApplicationCommandAutocomplete => {
-					if let Err(e) = slashie.autocomplete(self, data).await {
-						event!(
-							Level::ERROR,
-							error = &*e.root_cause(),
-							"error running autocomplete"
-						);
-					}
+
+				if let Some(settings) = self.user_settings(&data).await {
+					data.user_locale = settings.locale().map(ToOwned::to_owned);
+				}
+
+				if let Some(reason) = self.check_gates(&*slashie, &data) {
+					self.deny(data, reason).await;
+					return;
+				}
+
+				let started = Instant::now();
+				let result = slashie.run(self, data).await;
+				telemetry::command_invoked(&command.data.name, started.elapsed());
+
+				if let Err(e) = result {
+					self.report_error(command, &e).await;
+				}
+			}
+			InteractionType::ApplicationCommandAutocomplete => {
+				let focused = data
+					.command
+					.data
+					.focused_option()
+					.map(|option| option.name.clone());
+
+				if let Err(e) = slashie.autocomplete(self, data).await {
+					event!(
+						Level::ERROR,
+						error = &*e.root_cause(),
+						?focused,
+						"error running autocomplete"
+					);
 				}
-				_ => {}
 			}
+			_ => {}
+		}
+	}
+
+	// the interaction id is logged and shown to the user, so a report can be matched to the logs.
+	async fn report_error(self, command: ApplicationCommand, error: &miette::Report) {
+		let error_id = command.id;
+
+		event!(
+			Level::ERROR,
+			error = &*error.root_cause(),
+			%error_id,
+			"error running command"
+		);
+
+		let mut err_data = error_reply(command);
+
+		let result = if self.raw_get(&err_data).await.is_err() {
+			self.respond(&mut err_data).await.into_diagnostic()
 		} else {
-			event!(Level::WARN, "received unregistered command");
+			self.update(&mut err_data).await
+		};
+
+		if let Err(e) = result {
+			event!(Level::ERROR, error = ?e, %error_id, "error sending error response");
 		}
 	}
 
-	pub async fn ack(self, data: &SlashData) -> Result<(), HttpError> {
+	// shows a loading state, for commands that may take over 3 seconds. fill it in with `update`.
+	pub async fn defer(self, data: &SlashData) -> Result<(), HttpError> {
 		self.context()
 			.interaction_client()
 			.create_response(
 				data.command.id,
 				&data.command.token,
-				&InteractionResponse::DeferredChannelMessageWithSource(SlashData::BASE),
+				&deferred_response(data),
 			)
 			.exec()
 			.await?;
@@ -118,7 +287,9 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
-	pub async fn respond(self, data: &mut SlashData) -> Result<(), HttpError> {
+	pub async fn respond(self, data: &mut SlashData) -> Result<(), ResponseError> {
+		data.validate()?;
+
 		self.context()
 			.interaction_client()
 			.create_response(
@@ -136,21 +307,40 @@ impl InteractionsHelper {
 	}
 
 	pub async fn update(self, data: &mut SlashData) -> Result<()> {
-		let callback_data = mem::replace(&mut data.callback, SlashData::BASE);
-		let context = self.interaction_client();
-		let update_interaction = context.update_response(&data.command.token);
+		data.validate().into_diagnostic()?;
 
+		let callback_data = mem::replace(&mut data.callback, SlashData::BASE);
 		let bytes = serde_json::to_vec(&callback_data).into_diagnostic()?;
+		let request = update_request(&self.interaction_client(), &data.command.token, &bytes)
+			.into_diagnostic()?;
 
-		update_interaction
-			.payload_json(&bytes[..])
-			.exec()
+		self.http()
+			.request::<Message>(request)
 			.await
 			.into_diagnostic()?;
 
 		Ok(())
 	}
 
+	// sends an additional message after the initial response, returning it so it can be edited or
+	// deleted later.
+	pub async fn followup(self, data: &mut SlashData) -> Result<Message> {
+		data.validate().into_diagnostic()?;
+
+		let callback_data = mem::replace(&mut data.callback, SlashData::BASE);
+		let bytes = serde_json::to_vec(&callback_data).into_diagnostic()?;
+		let request = followup_request(&self.interaction_client(), &data.command.token, &bytes)
+			.into_diagnostic()?;
+
+		self.http()
+			.request::<Message>(request)
+			.await
+			.into_diagnostic()?
+			.model()
+			.await
+			.into_diagnostic()
+	}
+
 	pub async fn autocomplete(self, data: &mut SlashData) -> Result<(), HttpError> {
 		let autocomplete_data =
 			mem::replace(&mut data.autocomplete, Autocomplete { choices: vec![] });
@@ -168,6 +358,156 @@ impl InteractionsHelper {
 		Ok(())
 	}
 
+	pub async fn handle_component(self, component: MessageComponentInteraction) {
+		let handler = match Self::component_handlers().find(&component.data.custom_id) {
+			Some(handler) => handler,
+			None => return,
+		};
+
+		let custom_id = component.data.custom_id.clone();
+
+		if let Err(e) = handler(self, component).await {
+			event!(
+				Level::ERROR,
+				error = &*e.root_cause(),
+				%custom_id,
+				"error handling component"
+			);
+		}
+	}
+
+	// returns the reason the command isn't allowed to run, if any.
+	fn check_gates(self, slashie: &dyn SlashCommand, data: &SlashData) -> Option<String> {
+		let missing = missing_resources(
+			slashie.cache_needs(),
+			self.cache().config().resource_types(),
+		);
+		if !missing.is_empty() {
+			event!(
+				Level::ERROR,
+				?missing,
+				"command needs resources that aren't being cached"
+			);
+			return Some(DefaultMessages::Unavailable.to_string());
+		}
+
+		let required_permissions = slashie.required_permissions();
+		if !required_permissions.is_empty() {
+			if data.is_dm() {
+				return Some(DefaultMessages::GuildOnly.to_string());
+			}
+
+			match data.channel_permissions(&self) {
+				Ok(permissions) => {
+					if let Some(reason) = missing_permissions(permissions, required_permissions) {
+						return Some(reason);
+					}
+				}
+				Err(e) => {
+					event!(
+						Level::ERROR,
+						error = &*e.root_cause(),
+						"error getting user permissions"
+					);
+					return Some(DefaultMessages::PermissionDenied.to_string());
+				}
+			}
+		}
+
+		if let Some(cooldown) = slashie.cooldown() {
+			if let Err(remaining) = COOLDOWNS.check(
+				&data.command.data.name,
+				data.user_id(),
+				cooldown,
+				Instant::now(),
+			) {
+				return Some(DefaultMessages::Cooldown(remaining).to_string());
+			}
+		}
+
+		None
+	}
+
+	// does nothing if the guild hasn't set a log channel.
+	pub async fn log_to_guild(self, guild_id: Id<GuildMarker>, content: &str) -> Result<()> {
+		let settings = Tables::Guilds
+			.get_entry::<GuildSettings>(self.database(), &guild_id)
+			.await?;
+
+		let channel_id = match settings.log_channel() {
+			Some(channel_id) => channel_id,
+			None => return Ok(()),
+		};
+
+		self.http()
+			.create_message(channel_id)
+			.content(content)
+			.into_diagnostic()?
+			.exec()
+			.await
+			.into_diagnostic()?;
+
+		Ok(())
+	}
+
+	async fn guild_settings(self, data: &SlashData) -> Option<GuildSettings> {
+		let guild_id = data.command.guild_id?;
+
+		match Tables::Guilds
+			.get_entry::<GuildSettings>(self.database(), &guild_id)
+			.await
+		{
+			Ok(settings) => Some(settings),
+			Err(e) => {
+				event!(
+					Level::ERROR,
+					error = &*e.root_cause(),
+					"error getting guild settings"
+				);
+				None
+			}
+		}
+	}
+
+	// read only, users without an entry get the defaults. entries are only written by /preferences.
+	pub async fn user_settings(self, data: &SlashData) -> Option<UserSettings> {
+		let user_id = data.user_id();
+
+		match Tables::Users
+			.find_entry::<UserSettings>(self.database(), &user_id)
+			.await
+		{
+			Ok(settings) => Some(settings.unwrap_or_else(|| UserSettings::new(user_id))),
+			Err(e) => {
+				event!(
+					Level::ERROR,
+					error = &*e.root_cause(),
+					"error getting user settings"
+				);
+				None
+			}
+		}
+	}
+
+	async fn deny(self, mut data: SlashData, message: String) {
+		data.error(message);
+
+		if let Err(e) = self.respond(&mut data).await {
+			event!(Level::ERROR, error = ?e, "error responding to denied command");
+		}
+	}
+
+	// waits for the next message `author_id` sends in `channel_id`, for flows where the user replies
+	// in chat. the wait starts when this is called, not when the future is first polled.
+	pub fn wait_for_message(
+		self,
+		channel_id: Id<ChannelMarker>,
+		author_id: Id<UserMarker>,
+		limit: Duration,
+	) -> impl Future<Output = Result<Message, WaitError>> {
+		wait_for_message(self.standby(), channel_id, author_id, limit)
+	}
+
 	pub async fn raw_get(self, data: &SlashData) -> Result<Message> {
 		let http = self.interaction_client();
 		let get_original = http.response(&data.command.token);
@@ -175,17 +515,74 @@ impl InteractionsHelper {
 		model!(get_original).await.into_diagnostic()
 	}
 
-	fn match_command(name: &str, data: CommandData) -> Option<Box<dyn SlashCommand>> {
-		match name {
-			"ping" => Some(Box::new(Ping {})),
-			"crate" => Some(Box::new(Crate::parse(data).unwrap())),
-			"tag" => Some(Box::new(Tag::parse(data).unwrap())),
-			_ => None,
+	fn match_command(name: &str, data: CommandData) -> Option<Result<Box<dyn SlashCommand>>> {
+		Some(match name {
+			"ping" => boxed(Ping::parse(data)),
+			"crate" => boxed(Crate::parse(data)),
+			"tag" => boxed(Tag::parse(data)),
+			"inspect" => boxed(Inspect::parse(data)),
+			"help" => boxed(Help::parse(data)),
+			"stats" => boxed(Stats::parse(data)),
+			"preferences" => boxed(Preferences::parse(data)),
+			"settings" => boxed(Settings::parse(data)),
+			_ => return None,
+		})
+	}
+
+	pub(crate) fn get_slashies() -> [(Command, CommandScope); 8] {
+		[
+			slashie::<Ping>(),
+			slashie::<Crate>(),
+			slashie::<Tag>(),
+			slashie::<Inspect>(),
+			slashie::<Help>(),
+			slashie::<Stats>(),
+			slashie::<Preferences>(),
+			slashie::<Settings>(),
+		]
+	}
+
+	fn component_handlers() -> ComponentRegistry {
+		ComponentRegistry::new().register("paginator:", Paginator::expired)
+	}
+
+	// pairs each set of commands with where it's registered, `None` being globally. release builds
+	// only use the guild for `CommandScope::Guild` commands, debug builds put everything there since
+	// guild commands update instantly.
+	fn registration_targets(
+		guild_id: Option<Id<GuildMarker>>,
+		all_in_guild: bool,
+	) -> Vec<(Option<Id<GuildMarker>>, Vec<Command>)> {
+		let (global, guild) = Self::partition_slashies();
+
+		if all_in_guild && guild_id.is_some() {
+			return vec![(guild_id, global.into_iter().chain(guild).collect())];
 		}
+
+		if guild_id.is_some() {
+			return vec![(None, global), (guild_id, guild)];
+		}
+
+		if !guild.is_empty() {
+			event!(
+				Level::WARN,
+				"no guild id set, skipping registration of guild scoped commands"
+			);
+		}
+
+		vec![(None, global)]
 	}
 
-	fn get_slashies() -> [Command; 3] {
-		[Ping::define(), Crate::define(), Tag::define()].map(CommandBuilder::build)
+	// returns the global commands and the guild scoped commands.
+	fn partition_slashies() -> (Vec<Command>, Vec<Command>) {
+		let (global, guild): (Vec<_>, Vec<_>) = Self::get_slashies()
+			.into_iter()
+			.partition(|(_, scope)| *scope == CommandScope::Global);
+
+		(
+			global.into_iter().map(|(command, _)| command).collect(),
+			guild.into_iter().map(|(command, _)| command).collect(),
+		)
 	}
 }
 
@@ -194,3 +591,529 @@ impl QuickAccess for InteractionsHelper {
 		self.0.context()
 	}
 }
+
+fn missing_resources(needs: ResourceType, cached: ResourceType) -> ResourceType {
+	needs - cached
+}
+
+// keeps the flags already set, so a deferred ephemeral response stays ephemeral once updated.
+fn deferred_response(data: &SlashData) -> InteractionResponse {
+	InteractionResponse::DeferredChannelMessageWithSource(CallbackData {
+		flags: data.callback.flags,
+		..SlashData::BASE
+	})
+}
+
+// edits the original response, which is also how a deferred response is filled in.
+fn update_request(
+	client: &InteractionClient<'_>,
+	token: &str,
+	payload: &[u8],
+) -> Result<Request, HttpError> {
+	client
+		.update_response(token)
+		.payload_json(payload)
+		.try_into_request()
+}
+
+// follow-ups are sent through the interaction's webhook, authorized by its token.
+fn followup_request(
+	client: &InteractionClient<'_>,
+	token: &str,
+	payload: &[u8],
+) -> Result<Request, HttpError> {
+	client
+		.create_followup_message(token)
+		.payload_json(payload)
+		.try_into_request()
+}
+
+fn missing_permissions(granted: Permissions, required: Permissions) -> Option<String> {
+	if granted.contains(required) {
+		None
+	} else {
+		Some(DefaultMessages::MissingPermissions(required - granted).to_string())
+	}
+}
+
+fn blocked(settings: &GuildSettings, user_id: Id<UserMarker>) -> Option<String> {
+	settings
+		.is_blocked(user_id)
+		.then(|| DefaultMessages::Blocked.to_string())
+}
+
+fn boxed<T: SlashCommand + 'static>(command: Result<T>) -> Result<Box<dyn SlashCommand>> {
+	command.map(|command| Box::new(command) as Box<dyn SlashCommand>)
+}
+
+fn slashie<T: DefineCommand>() -> (Command, CommandScope) {
+	(T::define().build(), T::SCOPE)
+}
+
+fn wait_for_message(
+	standby: &Standby,
+	channel_id: Id<ChannelMarker>,
+	author_id: Id<UserMarker>,
+	limit: Duration,
+) -> impl Future<Output = Result<Message, WaitError>> {
+	let message = standby.wait_for_message(channel_id, move |event: &MessageCreate| {
+		event.author.id == author_id
+	});
+
+	async move {
+		match timeout(limit, message).await {
+			Ok(Ok(event)) => Ok(event.0),
+			Ok(Err(_)) => Err(WaitError::Canceled),
+			Err(_) => Err(WaitError::Timeout),
+		}
+	}
+}
+
+// the generic reply for a failed command, errors aren't shown to users as they may leak details.
+fn error_reply(command: ApplicationCommand) -> SlashData {
+	let error_id = command.id;
+	let mut data = SlashData::new(command);
+
+	data.error(format!(
+		"something went wrong running this command (error id `{}`)",
+		error_id
+	));
+
+	data
+}
+
+// every event logged while a command is handled falls under this span, so they can be correlated.
+fn command_span(
+	name: &str,
+	guild_id: Option<Id<GuildMarker>>,
+	user_id: Option<Id<UserMarker>>,
+) -> Span {
+	info_span!(
+		"command",
+		command.name = %name,
+		guild_id = ?guild_id.map(Id::get),
+		user_id = ?user_id.map(Id::get),
+	)
+}
+
+// compares what users see of each command, ignoring the ids and versions discord assigns.
+fn commands_match(current: &[Command], desired: &[Command]) -> bool {
+	let comparable = |commands: &[Command]| {
+		let mut commands = commands
+			.iter()
+			.map(|command| {
+				(
+					command.name.as_str(),
+					command.description.as_str(),
+					command.kind,
+					command.options.as_slice(),
+					command.default_permission,
+				)
+			})
+			.collect::<Vec<_>>();
+
+		commands.sort_by_key(|command| command.0);
+
+		commands
+	};
+
+	comparable(current) == comparable(desired)
+}
+
+// returns the commands to keep and the names of those removed, an empty list removes all of them.
+fn split_removed(current: Vec<Command>, names: &[String]) -> (Vec<Command>, Vec<String>) {
+	let (removed, kept): (Vec<_>, Vec<_>) = current
+		.into_iter()
+		.partition(|command| names.is_empty() || names.contains(&command.name));
+
+	(
+		kept,
+		removed.into_iter().map(|command| command.name).collect(),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		fmt::Debug,
+		sync::{Arc, Mutex},
+		time::Duration,
+	};
+
+	use miette::{IntoDiagnostic, Result};
+	use serde_json::json;
+	use tracing::{
+		field::{Field, Visit},
+		span::{Attributes, Id as SpanId},
+		subscriber::with_default,
+		Subscriber,
+	};
+	use tracing_subscriber::{
+		layer::{Context, SubscriberExt},
+		Layer, Registry,
+	};
+	use twilight_cache_inmemory::ResourceType;
+	use twilight_gateway::Event;
+	use twilight_http::{request::Method, Client};
+	use twilight_model::{
+		application::{
+			callback::InteractionResponse,
+			command::Command,
+			interaction::{ApplicationCommand, MessageComponentInteraction},
+		},
+		channel::{message::MessageFlags, Message},
+		gateway::payload::incoming::MessageCreate,
+		guild::Permissions,
+		id::{marker::GuildMarker, Id},
+	};
+	use twilight_standby::Standby;
+
+	use super::{
+		blocked, command_span, commands_match, deferred_response, error_reply, followup_request,
+		missing_permissions, missing_resources, split_removed, update_request, wait_for_message,
+		InteractionsHelper, WaitError,
+	};
+	use crate::{
+		settings::GuildSettings,
+		slashies::{Paginator, SlashData},
+		utils::DefaultMessages,
+	};
+
+	// records the fields of every span created while it's the active subscriber.
+	#[derive(Default)]
+	struct SpanFields(Arc<Mutex<Vec<String>>>);
+
+	impl<S: Subscriber> Layer<S> for SpanFields {
+		fn on_new_span(&self, attrs: &Attributes<'_>, _: &SpanId, _: Context<'_, S>) {
+			struct Visitor<'a>(&'a mut Vec<String>);
+
+			impl Visit for Visitor<'_> {
+				fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+					self.0.push(format!("{}={:?}", field.name(), value));
+				}
+			}
+
+			attrs.record(&mut Visitor(&mut self.0.lock().unwrap()));
+		}
+	}
+
+	#[test]
+	fn test_commands_match() {
+		let desired = InteractionsHelper::get_slashies().map(|(command, _)| command);
+
+		let mut current = desired.to_vec();
+		current.reverse();
+		for command in &mut current {
+			command.id = Some(Id::new(1));
+		}
+
+		assert!(commands_match(&current, &desired));
+
+		current[0].description = "Something else".to_owned();
+		assert!(!commands_match(&current, &desired));
+
+		assert!(!commands_match(&desired[1..], &desired));
+	}
+
+	#[test]
+	fn test_partition_slashies() {
+		let (global, guild) = InteractionsHelper::partition_slashies();
+		let names = |commands: &[Command]| {
+			commands
+				.iter()
+				.map(|command| command.name.clone())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(names(&guild), ["inspect"]);
+		assert_eq!(
+			names(&global),
+			[
+				"ping",
+				"crate",
+				"tag",
+				"help",
+				"stats",
+				"preferences",
+				"settings"
+			]
+		);
+	}
+
+	#[test]
+	fn test_registration_targets() {
+		let counts = |targets: Vec<(Option<Id<GuildMarker>>, Vec<Command>)>| {
+			targets
+				.into_iter()
+				.map(|(target, commands)| (target.map(Id::get), commands.len()))
+				.collect::<Vec<_>>()
+		};
+		let guild_id = Some(Id::new(1));
+
+		// release builds keep global commands global, only guild scoped ones use the guild.
+		assert_eq!(
+			counts(InteractionsHelper::registration_targets(guild_id, false)),
+			[(None, 7), (Some(1), 1)]
+		);
+		assert_eq!(
+			counts(InteractionsHelper::registration_targets(guild_id, true)),
+			[(Some(1), 8)]
+		);
+		assert_eq!(
+			counts(InteractionsHelper::registration_targets(None, false)),
+			[(None, 7)]
+		);
+		assert_eq!(
+			counts(InteractionsHelper::registration_targets(None, true)),
+			[(None, 7)]
+		);
+	}
+
+	#[test]
+	fn test_missing_permissions() {
+		let required = Permissions::MANAGE_GUILD | Permissions::MANAGE_MESSAGES;
+
+		assert!(missing_permissions(Permissions::all(), required).is_none());
+		assert!(missing_permissions(required, required).is_none());
+		assert_eq!(
+			missing_permissions(Permissions::MANAGE_GUILD, required),
+			Some(DefaultMessages::MissingPermissions(Permissions::MANAGE_MESSAGES).to_string())
+		);
+	}
+
+	#[test]
+	fn test_blocked_gate() {
+		let mut settings = GuildSettings::new(Id::new(1));
+
+		assert!(blocked(&settings, Id::new(2)).is_none());
+
+		settings.block_user(Id::new(2), None);
+		assert_eq!(
+			blocked(&settings, Id::new(2)),
+			Some(DefaultMessages::Blocked.to_string())
+		);
+		assert!(blocked(&settings, Id::new(3)).is_none());
+
+		settings.unblock_user(Id::new(2));
+		assert!(blocked(&settings, Id::new(2)).is_none());
+	}
+
+	#[test]
+	fn test_split_removed() {
+		let current = InteractionsHelper::get_slashies()
+			.map(|(command, _)| command)
+			.to_vec();
+		let names = |commands: &[Command]| {
+			commands
+				.iter()
+				.map(|command| command.name.clone())
+				.collect::<Vec<_>>()
+		};
+
+		let (kept, removed) = split_removed(current.clone(), &["tag".to_owned()]);
+		assert_eq!(removed, ["tag"]);
+		assert_eq!(kept.len(), current.len() - 1);
+		assert!(!names(&kept).contains(&"tag".to_owned()));
+
+		let (kept, removed) = split_removed(current.clone(), &[]);
+		assert!(kept.is_empty());
+		assert_eq!(removed, names(&current));
+
+		let (kept, removed) = split_removed(current.clone(), &["missing".to_owned()]);
+		assert!(removed.is_empty());
+		assert_eq!(kept.len(), current.len());
+	}
+
+	#[test]
+	fn test_command_span_fields() {
+		let layer = SpanFields::default();
+		let fields = Arc::clone(&layer.0);
+
+		with_default(Registry::default().with(layer), || {
+			let _span = command_span("inspect", Some(Id::new(1)), Some(Id::new(2)));
+		});
+
+		let fields = fields.lock().unwrap();
+		assert_eq!(
+			*fields,
+			[
+				"command.name=inspect",
+				"guild_id=Some(1)",
+				"user_id=Some(2)"
+			]
+		);
+	}
+
+	fn message_json(author_id: u64, content: &str) -> serde_json::Value {
+		json!({
+			"attachments": [],
+			"author": {
+				"avatar": null,
+				"discriminator": "0001",
+				"id": author_id.to_string(),
+				"username": "star",
+			},
+			"channel_id": "1",
+			"content": content,
+			"edited_timestamp": null,
+			"embeds": [],
+			"id": "3",
+			"mention_everyone": false,
+			"mention_roles": [],
+			"mentions": [],
+			"pinned": false,
+			"timestamp": "2021-01-01T00:00:00.000000+00:00",
+			"tts": false,
+			"type": 0,
+		})
+	}
+
+	fn message_create(author_id: u64, content: &str) -> Event {
+		let message: Message = serde_json::from_value(message_json(author_id, content)).unwrap();
+
+		Event::MessageCreate(Box::new(MessageCreate(message)))
+	}
+
+	#[tokio::test]
+	async fn test_wait_for_message() {
+		let standby = Standby::new();
+		let reply = wait_for_message(&standby, Id::new(1), Id::new(2), Duration::from_secs(5));
+
+		standby.process(&message_create(4, "someone else"));
+		standby.process(&message_create(2, "!"));
+
+		assert_eq!(
+			reply.await.map(|message| message.content).as_deref(),
+			Ok("!")
+		);
+
+		let reply = wait_for_message(&standby, Id::new(1), Id::new(2), Duration::from_millis(10));
+
+		assert_eq!(reply.await.map(|_| ()), Err(WaitError::Timeout));
+	}
+
+	#[test]
+	fn test_missing_resources() {
+		let cached = ResourceType::all() - ResourceType::GUILD;
+
+		assert_eq!(
+			missing_resources(ResourceType::GUILD | ResourceType::USER, cached),
+			ResourceType::GUILD
+		);
+		assert!(missing_resources(ResourceType::USER, cached).is_empty());
+		assert!(missing_resources(ResourceType::empty(), ResourceType::empty()).is_empty());
+	}
+
+	fn command(name: &str) -> ApplicationCommand {
+		serde_json::from_value(json!({
+			"application_id": "1",
+			"channel_id": "2",
+			"data": {
+				"id": "3",
+				"name": name,
+				"type": 1,
+			},
+			"id": "4",
+			"locale": "en-US",
+			"token": "token",
+			"type": 2,
+			"user": {
+				"avatar": null,
+				"discriminator": "0001",
+				"id": "5",
+				"username": "star",
+			},
+		}))
+		.unwrap()
+	}
+
+	#[test]
+	fn test_defer_then_update() -> Result<()> {
+		let mut data = SlashData::new(command("tag"));
+		data.ephemeral();
+
+		match deferred_response(&data) {
+			InteractionResponse::DeferredChannelMessageWithSource(callback) => {
+				assert_eq!(callback.flags, Some(MessageFlags::EPHEMERAL));
+				assert!(callback.content.is_none());
+			}
+			response => panic!("expected a deferred response, got {:?}", response),
+		}
+
+		let http = Client::new("token".to_owned());
+		let client = http.interaction(Id::new(1));
+		data.message("done".to_owned());
+		let payload = serde_json::to_vec(&data.callback).into_diagnostic()?;
+		let request = update_request(&client, &data.command.token, &payload).into_diagnostic()?;
+
+		assert_eq!(request.method(), Method::Patch);
+		assert_eq!(request.path(), "webhooks/1/token/messages/@original");
+		assert_eq!(request.body(), Some(&payload[..]));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_followup_webhook() -> Result<()> {
+		let mut data = SlashData::new(command("tag"));
+		data.message("more".to_owned());
+
+		let http = Client::new("token".to_owned());
+		let client = http.interaction(Id::new(1));
+		let payload = serde_json::to_vec(&data.callback).into_diagnostic()?;
+		let request = followup_request(&client, &data.command.token, &payload).into_diagnostic()?;
+
+		// the application's webhook, not the original response or the interaction callback.
+		assert_eq!(request.method(), Method::Post);
+		assert!(request.path().starts_with("webhooks/1/token"));
+		assert!(!request.path().contains("@original"));
+		assert_eq!(request.body(), Some(&payload[..]));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_error_reply() {
+		let reply = error_reply(command("tag"));
+		let description = reply
+			.callback
+			.embeds
+			.as_ref()
+			.and_then(|embeds| embeds.first())
+			.and_then(|embed| embed.description.as_deref());
+
+		assert_eq!(
+			description,
+			Some("something went wrong running this command (error id `4`)")
+		);
+		assert!(reply.callback.flags.is_some());
+	}
+
+	#[test]
+	fn test_component_dispatch() {
+		let component: MessageComponentInteraction = serde_json::from_value(json!({
+			"application_id": "1",
+			"channel_id": "1",
+			"data": {
+				"component_type": 2,
+				"custom_id": "paginator:next",
+			},
+			"id": "4",
+			"locale": "en-US",
+			"message": message_json(2, ""),
+			"token": "token",
+			"type": 3,
+		}))
+		.unwrap();
+
+		let handler = InteractionsHelper::component_handlers().find(&component.data.custom_id);
+
+		assert_eq!(
+			handler.map(|handler| handler as usize),
+			Some(Paginator::expired as usize)
+		);
+		assert!(InteractionsHelper::component_handlers()
+			.find("unknown:1")
+			.is_none());
+	}
+}
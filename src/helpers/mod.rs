@@ -5,7 +5,10 @@ mod interactions;
 pub mod parsing;
 pub mod playground;
 
-pub use self::{color::Color, interactions::InteractionsHelper};
+pub use self::{
+	color::Color,
+	interactions::{InteractionsHelper, WaitError},
+};
 
 pub const STARLIGHT_COLORS: [Color; 3] = [
 	Color::new(132, 61, 164),
@@ -13,6 +16,8 @@ pub const STARLIGHT_COLORS: [Color; 3] = [
 	Color::new(183, 47, 0),
 ];
 
+pub const ERROR_COLOR: Color = Color::new(237, 66, 69);
+
 #[derive(Debug, Clone, Copy)]
 #[must_use = "Helpers do nothing if not used"]
 pub struct Helpers {
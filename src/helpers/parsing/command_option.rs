@@ -1,4 +1,12 @@
-use twilight_model::application::interaction::application_command::CommandOptionValue;
+use twilight_model::{
+	application::interaction::application_command::{
+		CommandData, CommandDataOption, CommandOptionValue,
+	},
+	id::{
+		marker::{ChannelMarker, RoleMarker, UserMarker},
+		Id,
+	},
+};
 
 pub trait CommandParse<T> {
 	// to avoid naming conflicts with anything else that could use parse..
@@ -34,3 +42,166 @@ impl CommandParse<bool> for CommandOptionValue {
 		}
 	}
 }
+
+impl CommandParse<Id<UserMarker>> for CommandOptionValue {
+	fn parse_option(self) -> Option<Id<UserMarker>> {
+		if let Self::User(u) = self {
+			Some(u)
+		} else {
+			None
+		}
+	}
+}
+
+// looks options up by name, returning `None` if the option is missing or of a different type.
+pub trait CommandOptions {
+	fn data_options(&self) -> &[CommandDataOption];
+
+	fn option_value(&self, name: &str) -> Option<&CommandOptionValue> {
+		self.data_options()
+			.iter()
+			.find(|opt| opt.name == name)
+			.map(|opt| &opt.value)
+	}
+
+	fn string_option(&self, name: &str) -> Option<&str> {
+		match self.option_value(name)? {
+			CommandOptionValue::String(v) => Some(v.as_str()),
+			_ => None,
+		}
+	}
+
+	fn integer_option(&self, name: &str) -> Option<i64> {
+		match self.option_value(name)? {
+			CommandOptionValue::Integer(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	fn number_option(&self, name: &str) -> Option<f64> {
+		match self.option_value(name)? {
+			CommandOptionValue::Number(v) => Some(v.0),
+			_ => None,
+		}
+	}
+
+	fn boolean_option(&self, name: &str) -> Option<bool> {
+		match self.option_value(name)? {
+			CommandOptionValue::Boolean(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	fn user_option(&self, name: &str) -> Option<Id<UserMarker>> {
+		match self.option_value(name)? {
+			CommandOptionValue::User(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	fn channel_option(&self, name: &str) -> Option<Id<ChannelMarker>> {
+		match self.option_value(name)? {
+			CommandOptionValue::Channel(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	fn role_option(&self, name: &str) -> Option<Id<RoleMarker>> {
+		match self.option_value(name)? {
+			CommandOptionValue::Role(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	// the option being typed in during autocomplete, looking inside subcommands too.
+	fn focused_option(&self) -> Option<&CommandDataOption> {
+		self.data_options().iter().find_map(|opt| match &opt.value {
+			CommandOptionValue::SubCommand(options)
+			| CommandOptionValue::SubCommandGroup(options) => options.as_slice().focused_option(),
+			_ if opt.focused => Some(opt),
+			_ => None,
+		})
+	}
+}
+
+impl CommandOptions for [CommandDataOption] {
+	fn data_options(&self) -> &[CommandDataOption] {
+		self
+	}
+}
+
+impl CommandOptions for CommandData {
+	fn data_options(&self) -> &[CommandDataOption] {
+		&self.options
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::{
+		application::{
+			command::Number,
+			interaction::application_command::{CommandDataOption, CommandOptionValue},
+		},
+		id::Id,
+	};
+
+	use super::CommandOptions;
+
+	fn option(name: &str, value: CommandOptionValue) -> CommandDataOption {
+		CommandDataOption {
+			focused: false,
+			name: name.to_owned(),
+			value,
+		}
+	}
+
+	#[test]
+	fn test_typed_options() {
+		let options = vec![
+			option("name", CommandOptionValue::String("starlight".to_owned())),
+			option("count", CommandOptionValue::Integer(5)),
+			option("ratio", CommandOptionValue::Number(Number(0.5))),
+			option("ephemeral", CommandOptionValue::Boolean(true)),
+			option("user", CommandOptionValue::User(Id::new(1))),
+			option("channel", CommandOptionValue::Channel(Id::new(2))),
+			option("role", CommandOptionValue::Role(Id::new(3))),
+		];
+
+		assert_eq!(options.string_option("name"), Some("starlight"));
+		assert_eq!(options.integer_option("count"), Some(5));
+		assert!((options.number_option("ratio").unwrap_or_default() - 0.5).abs() < f64::EPSILON);
+		assert_eq!(options.boolean_option("ephemeral"), Some(true));
+		assert_eq!(options.user_option("user"), Some(Id::new(1)));
+		assert_eq!(options.channel_option("channel"), Some(Id::new(2)));
+		assert_eq!(options.role_option("role"), Some(Id::new(3)));
+	}
+
+	#[test]
+	fn test_missing_or_mismatched_options() {
+		let options = vec![option("count", CommandOptionValue::Integer(5))];
+
+		assert_eq!(options.string_option("count"), None);
+		assert_eq!(options.integer_option("missing"), None);
+	}
+
+	#[test]
+	fn test_focused_option() {
+		let options = vec![option(
+			"add",
+			CommandOptionValue::SubCommand(vec![
+				option("name", CommandOptionValue::String("star".to_owned())),
+				CommandDataOption {
+					focused: true,
+					..option("content", CommandOptionValue::String("li".to_owned()))
+				},
+			]),
+		)];
+
+		assert_eq!(
+			options.focused_option().map(|opt| opt.name.as_str()),
+			Some("content")
+		);
+		assert!(options[0..0].focused_option().is_none());
+	}
+}
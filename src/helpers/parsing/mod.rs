@@ -3,5 +3,5 @@ mod command_option;
 
 pub use self::{
 	codeblock::{CodeBlock, CodeBlockError},
-	command_option::CommandParse,
+	command_option::{CommandOptions, CommandParse},
 };
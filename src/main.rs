@@ -1,19 +1,13 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use clap::Parser;
 use dotenv::dotenv;
 use starlight::{
 	prelude::*,
 	state::{Config, ContextBuilder, State},
 };
 use tokio::runtime::Builder;
-#[cfg(unix)]
-use tokio::signal::unix::{signal, SignalKind};
-#[cfg(windows)]
-use tokio::signal::windows::{ctrl_break, ctrl_c};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 use twilight_cache_inmemory::{InMemoryCacheBuilder, ResourceType};
-use twilight_gateway::Intents;
 
 static THREAD_ID: AtomicUsize = AtomicUsize::new(1);
 
@@ -55,10 +49,10 @@ async fn run() -> Result<()> {
 		.try_init()
 		.into_diagnostic()?;
 
-	let config = Config::parse();
+	let config = Config::load()?;
 	let (client, events) = ContextBuilder::new()
 		.config(config)
-		.intents(Intents::from_bits(3).unwrap_or_else(Intents::all))
+		.intents(Config::default_intents())
 		.shard_builder(|b| b)?
 		.cache(InMemoryCacheBuilder::new().resource_types(ResourceType::all()))
 		.database_path("./target/db")
@@ -67,32 +61,7 @@ async fn run() -> Result<()> {
 
 	client.connect().await?;
 
-	#[cfg(windows)]
-	{
-		let mut sig_c = ctrl_c().into_diagnostic()?;
-		let mut sig_break = ctrl_break().into_diagnostic()?;
-		tokio::select! {
-			_ = sig_c.recv() => event!(Level::INFO, "received CTRLC"),
-			_ = sig_break.recv() => event!(Level::INFO, "received CTRLBREAK"),
-			_ = client.process(events) => (),
-		};
-	}
-
-	#[cfg(unix)]
-	{
-		let mut sigint = signal(SignalKind::interrupt()).into_diagnostic()?;
-		let mut sigterm = signal(SignalKind::terminate()).into_diagnostic()?;
-
-		tokio::select! {
-			_ = sigint.recv() => event!(Level::INFO, "received SIGINT"),
-			_ = sigterm.recv() => event!(Level::INFO, "received SIGTERM"),
-			_ = client.process(events) => (),
-		};
-	}
-
-	event!(Level::INFO, "shutting down");
-
-	client.shutdown();
+	client.run_until_shutdown(events).await?;
 
 	let client_ptr = unsafe { Box::from_raw(client.0 as *const State as *mut State) };
 
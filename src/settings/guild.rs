@@ -3,15 +3,24 @@ use std::iter::Extend;
 use serde::{Deserialize, Serialize};
 use starchart::IndexEntry;
 use twilight_model::id::{
-	marker::{GuildMarker, UserMarker},
+	marker::{ChannelMarker, GuildMarker, UserMarker},
 	Id,
 };
 
+// entries written before a field existed are still on disk, so every field added after `id` needs
+// `#[serde(default)]` to fill it in when those entries are loaded.
 #[allow(clippy::unsafe_derive_deserialize)]
 #[derive(Debug, Clone, IndexEntry, Serialize, Deserialize)]
 pub struct GuildSettings {
 	id: Id<GuildMarker>,
+	#[serde(default)]
 	tags: Vec<GuildTag>,
+	#[serde(default)]
+	blocked_users: Vec<BlockedUser>,
+	#[serde(default)]
+	locale: Option<String>,
+	#[serde(default)]
+	log_channel: Option<Id<ChannelMarker>>,
 }
 
 impl GuildSettings {
@@ -20,6 +29,9 @@ impl GuildSettings {
 		Self {
 			id,
 			tags: Vec::new(),
+			blocked_users: Vec::new(),
+			locale: None,
+			log_channel: None,
 		}
 	}
 
@@ -46,6 +58,54 @@ impl GuildSettings {
 	pub fn tags_mut(&mut self) -> &mut [GuildTag] {
 		&mut self.tags
 	}
+
+	#[must_use]
+	pub fn blocked_users(&self) -> &[BlockedUser] {
+		&self.blocked_users
+	}
+
+	#[must_use]
+	pub fn is_blocked(&self, user_id: Id<UserMarker>) -> bool {
+		self.blocked_users.iter().any(|user| user.id == user_id)
+	}
+
+	// returns false if the user was already blocked, keeping the original reason.
+	pub fn block_user(&mut self, user_id: Id<UserMarker>, reason: Option<String>) -> bool {
+		if self.is_blocked(user_id) {
+			return false;
+		}
+
+		self.blocked_users.push(BlockedUser::new(user_id, reason));
+
+		true
+	}
+
+	#[must_use]
+	pub fn locale(&self) -> Option<&str> {
+		self.locale.as_deref()
+	}
+
+	pub fn set_locale(&mut self, locale: Option<String>) {
+		self.locale = locale;
+	}
+
+	#[must_use]
+	pub const fn log_channel(&self) -> Option<Id<ChannelMarker>> {
+		self.log_channel
+	}
+
+	pub fn set_log_channel(&mut self, channel_id: Option<Id<ChannelMarker>>) {
+		self.log_channel = channel_id;
+	}
+
+	// returns false if the user wasn't blocked.
+	pub fn unblock_user(&mut self, user_id: Id<UserMarker>) -> bool {
+		let length = self.blocked_users.len();
+
+		self.blocked_users.retain(|user| user.id != user_id);
+
+		self.blocked_users.len() != length
+	}
 }
 
 impl Default for GuildSettings {
@@ -55,6 +115,9 @@ impl Default for GuildSettings {
 		Self {
 			id: unsafe { Id::new_unchecked(1) },
 			tags: default_tags,
+			blocked_users: Vec::new(),
+			locale: None,
+			log_channel: None,
 		}
 	}
 }
@@ -113,3 +176,112 @@ impl Default for GuildTag {
 		}
 	}
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockedUser {
+	id: Id<UserMarker>,
+	reason: Option<String>,
+}
+
+impl BlockedUser {
+	#[must_use]
+	pub const fn new(id: Id<UserMarker>, reason: Option<String>) -> Self {
+		Self { id, reason }
+	}
+
+	#[must_use]
+	pub const fn id(&self) -> Id<UserMarker> {
+		self.id
+	}
+
+	#[must_use]
+	pub fn reason(&self) -> Option<&str> {
+		self.reason.as_deref()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use miette::{IntoDiagnostic, Result};
+	use twilight_model::id::Id;
+
+	use super::GuildSettings;
+
+	#[test]
+	fn test_load_old_entry() -> Result<()> {
+		let settings: GuildSettings = toml::from_str(r#"id = "123""#).into_diagnostic()?;
+
+		assert_eq!(settings.id().get(), 123);
+		assert!(settings.tags().is_empty());
+		assert!(settings.blocked_users().is_empty());
+		assert!(settings.locale().is_none());
+		assert!(settings.log_channel().is_none());
+
+		Ok(())
+	}
+
+	// debug builds write pretty toml and release builds compact toml, both have to read the other.
+	#[test]
+	fn test_pretty_and_compact_roundtrip() -> Result<()> {
+		let mut settings = GuildSettings::new(Id::new(123));
+		assert!(settings.block_user(Id::new(456), Some("spam".to_owned())));
+		settings.set_locale(Some("fr".to_owned()));
+		settings.set_log_channel(Some(Id::new(789)));
+
+		let compact = toml::to_string(&settings).into_diagnostic()?;
+		let pretty = toml::to_string_pretty(&settings).into_diagnostic()?;
+
+		let from_pretty: GuildSettings = toml::from_str(&pretty).into_diagnostic()?;
+		let from_compact: GuildSettings = toml::from_str(&compact).into_diagnostic()?;
+
+		assert_eq!(toml::to_string(&from_pretty).into_diagnostic()?, compact);
+		assert_eq!(
+			toml::to_string_pretty(&from_compact).into_diagnostic()?,
+			pretty
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_block_users() {
+		let mut settings = GuildSettings::new(Id::new(1));
+		let user_id = Id::new(2);
+
+		assert!(settings.block_user(user_id, Some("spam".to_owned())));
+		assert!(!settings.block_user(user_id, None));
+		assert_eq!(settings.blocked_users().len(), 1);
+		assert_eq!(settings.blocked_users()[0].reason(), Some("spam"));
+		assert!(settings.is_blocked(user_id));
+		assert!(!settings.is_blocked(Id::new(3)));
+
+		assert!(settings.unblock_user(user_id));
+		assert!(!settings.unblock_user(user_id));
+		assert!(!settings.is_blocked(user_id));
+	}
+
+	#[test]
+	fn test_locale() -> Result<()> {
+		let mut settings = GuildSettings::new(Id::new(1));
+
+		settings.set_locale(Some("de".to_owned()));
+		assert_eq!(settings.locale(), Some("de"));
+
+		let serialized = toml::to_string(&settings).into_diagnostic()?;
+		let settings: GuildSettings = toml::from_str(&serialized).into_diagnostic()?;
+		assert_eq!(settings.locale(), Some("de"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_log_channel() {
+		let mut settings = GuildSettings::new(Id::new(1));
+
+		settings.set_log_channel(Some(Id::new(2)));
+		assert_eq!(settings.log_channel(), Some(Id::new(2)));
+
+		settings.set_log_channel(None);
+		assert!(settings.log_channel().is_none());
+	}
+}
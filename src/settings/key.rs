@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use twilight_model::id::{
+	marker::{GuildMarker, UserMarker},
+	Id,
+};
+
+use crate::prelude::*;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum KeyParseError {
+	#[error("the key is missing the `-` separator")]
+	MissingSeparator,
+	#[error("a part of the key isn't a valid id")]
+	InvalidId,
+}
+
+// keys entries scoped to a member of a guild. ids are only digits, so joining them with `-` can't
+// make two different pairs produce the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MemberKey {
+	pub guild_id: Id<GuildMarker>,
+	pub user_id: Id<UserMarker>,
+}
+
+impl MemberKey {
+	#[must_use]
+	pub const fn new(guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) -> Self {
+		Self { guild_id, user_id }
+	}
+}
+
+impl Display for MemberKey {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}-{}", self.guild_id, self.user_id)
+	}
+}
+
+impl FromStr for MemberKey {
+	type Err = KeyParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (guild_id, user_id) = s.split_once('-').ok_or(KeyParseError::MissingSeparator)?;
+
+		let parse = |id: &str| {
+			id.parse()
+				.ok()
+				.and_then(Id::new_checked)
+				.ok_or(KeyParseError::InvalidId)
+		};
+
+		Ok(Self::new(parse(guild_id)?, parse(user_id)?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::id::Id;
+
+	use super::{KeyParseError, MemberKey};
+
+	#[test]
+	fn test_member_key() {
+		let key = MemberKey::new(Id::new(12), Id::new(3));
+
+		assert_eq!(key.to_string(), "12-3");
+		assert_ne!(
+			key.to_string(),
+			MemberKey::new(Id::new(1), Id::new(23)).to_string()
+		);
+		assert_eq!("12-3".parse(), Ok(key));
+
+		assert_eq!(
+			"123".parse::<MemberKey>(),
+			Err(KeyParseError::MissingSeparator)
+		);
+		assert_eq!("0-3".parse::<MemberKey>(), Err(KeyParseError::InvalidId));
+		assert_eq!("12-3-4".parse::<MemberKey>(), Err(KeyParseError::InvalidId));
+	}
+}
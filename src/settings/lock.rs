@@ -0,0 +1,72 @@
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, PoisonError},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use super::Tables;
+use crate::prelude::*;
+
+// one lock per table and key, so writes to different guilds never wait on each other.
+// locks nobody holds are pruned whenever a new one is taken.
+#[derive(Debug, Default)]
+pub struct EntryLocks(Mutex<BTreeMap<(Tables, String), Arc<AsyncMutex<()>>>>);
+
+impl EntryLocks {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(Mutex::new(BTreeMap::new()))
+	}
+
+	pub async fn lock<K: Display>(&self, table: Tables, key: &K) -> OwnedMutexGuard<()> {
+		let lock = {
+			let mut locks = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+			locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+
+			Arc::clone(locks.entry((table, key.to_string())).or_default())
+		};
+
+		lock.lock_owned().await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::Arc, time::Duration};
+
+	use tokio::{sync::Mutex, time::sleep};
+
+	use super::EntryLocks;
+	use crate::settings::Tables;
+
+	#[tokio::test]
+	async fn test_concurrent_updates() {
+		let locks = Arc::new(EntryLocks::new());
+		let value = Arc::new(Mutex::new(Vec::new()));
+
+		let update = |item: &'static str| {
+			let locks = Arc::clone(&locks);
+			let value = Arc::clone(&value);
+
+			tokio::spawn(async move {
+				let _guard = locks.lock(Tables::Guilds, &1_u64).await;
+
+				let mut read = value.lock().await.clone();
+				sleep(Duration::from_millis(10)).await;
+				read.push(item);
+				*value.lock().await = read;
+			})
+		};
+
+		let (first, second) = tokio::join!(update("first"), update("second"));
+		first.unwrap();
+		second.unwrap();
+
+		let mut value = value.lock().await.clone();
+		value.sort_unstable();
+
+		assert_eq!(value, ["first", "second"]);
+	}
+}
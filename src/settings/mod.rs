@@ -1,21 +1,45 @@
 mod guild;
+mod key;
+mod lock;
+mod user;
+
+use std::time::Duration;
+
 use starchart::{
-	action::{ActionError, CreateTableAction, ReadEntryAction, UpdateEntryAction},
+	action::{
+		ActionError, CreateEntryAction, CreateTableAction, DeleteEntryAction, ReadEntryAction,
+		UpdateEntryAction,
+	},
 	Action, IndexEntry, Starchart,
 };
+use tokio::sync::OwnedMutexGuard;
+
+pub use self::{
+	guild::{BlockedUser, GuildSettings, GuildTag},
+	key::{KeyParseError, MemberKey},
+	lock::EntryLocks,
+	user::UserSettings,
+};
+use crate::{
+	prelude::*,
+	state::{Context, PendingTasks},
+};
 
-pub use self::guild::{GuildSettings, GuildTag};
-use crate::{prelude::*, state::Context};
+static ENTRY_LOCKS: EntryLocks = EntryLocks::new();
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+static PENDING_WRITES: PendingTasks = PendingTasks::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Tables {
 	Guilds,
+	Users,
 }
 
 impl Tables {
 	#[instrument(skip(context))]
 	pub async fn init(context: Context) -> Result<(), ActionError> {
 		Self::init_guilds(context).await?;
+		Self::init_users(context).await?;
 		Ok(())
 	}
 
@@ -26,16 +50,40 @@ impl Tables {
 	) -> Result<T>
 	where
 		<T as IndexEntry>::Key: Sync + Display,
+	{
+		self.find_entry(chart, key)
+			.await?
+			.ok_or_else(|| error!("could not find entry with key {}", key))
+	}
+
+	// like `get_entry`, but a missing entry is `None` instead of an error.
+	pub async fn find_entry<T: IndexEntry>(
+		self,
+		chart: &Starchart<TomlBackend>,
+		key: &<T as IndexEntry>::Key,
+	) -> Result<Option<T>>
+	where
+		<T as IndexEntry>::Key: Sync,
 	{
 		let mut action: ReadEntryAction<T> = Action::new();
 		let table = self.to_string();
 		action.set_table(&table).set_key(key);
 
-		action
-			.run_read_entry(chart)
-			.await
-			.into_diagnostic()?
-			.ok_or_else(|| error!("could not find entry with key {}", key))
+		action.run_read_entry(chart).await.into_diagnostic()
+	}
+
+	pub async fn create_entry<T: IndexEntry>(
+		self,
+		chart: &Starchart<TomlBackend>,
+		entry: &T,
+	) -> Result<()> {
+		let _write = PENDING_WRITES.track();
+
+		let mut action: CreateEntryAction<T> = Action::new();
+		let table = self.to_string();
+		action.set_table(&table).set_entry(entry);
+
+		action.run_create_entry(chart).await.into_diagnostic()
 	}
 
 	pub async fn update_entry<T: IndexEntry>(
@@ -43,6 +91,8 @@ impl Tables {
 		chart: &Starchart<TomlBackend>,
 		entry: &T,
 	) -> Result<()> {
+		let _write = PENDING_WRITES.track();
+
 		let mut action: UpdateEntryAction<T> = Action::new();
 		let table = self.to_string();
 		action.set_table(&table).set_entry(entry);
@@ -50,6 +100,57 @@ impl Tables {
 		action.run_update_entry(chart).await.into_diagnostic()
 	}
 
+	// hold this across a read and the following update, otherwise two commands editing the same
+	// entry at once can each overwrite the other's change.
+	pub async fn lock<K: Display>(self, key: &K) -> OwnedMutexGuard<()> {
+		ENTRY_LOCKS.lock(self, key).await
+	}
+
+	pub async fn modify_entry<T, R, F>(
+		self,
+		chart: &Starchart<TomlBackend>,
+		key: &<T as IndexEntry>::Key,
+		f: F,
+	) -> Result<R>
+	where
+		T: IndexEntry,
+		<T as IndexEntry>::Key: Sync + Display,
+		F: FnOnce(&mut T) -> R + Send,
+	{
+		let _guard = self.lock(key).await;
+
+		let mut entry = self.get_entry::<T>(chart, key).await?;
+
+		let output = f(&mut entry);
+
+		self.update_entry(chart, &entry).await?;
+
+		Ok(output)
+	}
+
+	// returns whether an entry was removed.
+	pub async fn delete_entry<T: IndexEntry>(
+		self,
+		chart: &Starchart<TomlBackend>,
+		key: &<T as IndexEntry>::Key,
+	) -> Result<bool>
+	where
+		<T as IndexEntry>::Key: Sync,
+	{
+		let _write = PENDING_WRITES.track();
+
+		let mut action: DeleteEntryAction<T> = Action::new();
+		let table = self.to_string();
+		action.set_table(&table).set_key(key);
+
+		action.run_delete_entry(chart).await.into_diagnostic()
+	}
+
+	// waits for the writes in progress, returning whether they all finished before the timeout.
+	pub async fn flush(limit: Duration) -> bool {
+		PENDING_WRITES.wait(limit).await
+	}
+
 	async fn init_guilds(context: Context) -> Result<(), ActionError> {
 		let default = GuildSettings::default();
 		event!(Level::INFO, ?default, "creating table guilds");
@@ -63,12 +164,74 @@ impl Tables {
 
 		Ok(())
 	}
+
+	async fn init_users(context: Context) -> Result<(), ActionError> {
+		event!(Level::INFO, "creating table users");
+		let mut action: CreateTableAction<UserSettings> = Action::new();
+		let users_table = Self::Users.to_string();
+		action.set_table(&users_table);
+
+		action.run_create_table(context.database()).await?;
+
+		Ok(())
+	}
 }
 
 impl Display for Tables {
 	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
 		match self {
 			Self::Guilds => f.write_str("guilds"),
+			Self::Users => f.write_str("users"),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use starchart::{action::CreateTableAction, Action, Starchart};
+	use twilight_model::id::Id;
+
+	use super::{Tables, UserSettings, PENDING_WRITES};
+	use crate::prelude::*;
+
+	#[tokio::test]
+	async fn test_flush_waits_for_writes() -> Result<()> {
+		let path = std::env::temp_dir().join(format!("starlight-flush-{}", std::process::id()));
+		let chart = Starchart::new(TomlBackend::new(&path).into_diagnostic()?)
+			.await
+			.into_diagnostic()?;
+
+		let mut action: CreateTableAction<UserSettings> = Action::new();
+		let table = Tables::Users.to_string();
+		action.set_table(&table);
+		action.run_create_table(&chart).await.into_diagnostic()?;
+
+		let user_id = Id::new(1);
+		// taken before spawning, so the write is pending whenever the flush starts.
+		let pending = PENDING_WRITES.track();
+		let write = tokio::spawn({
+			let chart = chart.clone();
+			async move {
+				let _pending = pending;
+
+				Tables::Users
+					.create_entry(&chart, &UserSettings::new(user_id))
+					.await
+			}
+		});
+
+		assert!(Tables::flush(Duration::from_secs(5)).await);
+
+		let entry = Tables::Users
+			.find_entry::<UserSettings>(&chart, &user_id)
+			.await?;
+		assert_eq!(entry.map(|entry| entry.id()), Some(user_id));
+
+		write.await.into_diagnostic()??;
+		std::fs::remove_dir_all(path).into_diagnostic()?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use starchart::IndexEntry;
+use twilight_model::id::{marker::UserMarker, Id};
+
+// settings that follow a user across guilds. like `GuildSettings`, every field after `id` needs
+// `#[serde(default)]` so older entries keep loading.
+#[derive(Debug, Clone, IndexEntry, Serialize, Deserialize)]
+pub struct UserSettings {
+	id: Id<UserMarker>,
+	#[serde(default)]
+	locale: Option<String>,
+	#[serde(default)]
+	dm_opt_out: bool,
+}
+
+impl UserSettings {
+	#[must_use]
+	pub const fn new(id: Id<UserMarker>) -> Self {
+		Self {
+			id,
+			locale: None,
+			dm_opt_out: false,
+		}
+	}
+
+	#[must_use]
+	pub const fn id(&self) -> Id<UserMarker> {
+		self.id
+	}
+
+	#[must_use]
+	pub fn locale(&self) -> Option<&str> {
+		self.locale.as_deref()
+	}
+
+	pub fn set_locale(&mut self, locale: Option<String>) {
+		self.locale = locale;
+	}
+
+	#[must_use]
+	pub const fn dm_opt_out(&self) -> bool {
+		self.dm_opt_out
+	}
+
+	pub fn set_dm_opt_out(&mut self, dm_opt_out: bool) {
+		self.dm_opt_out = dm_opt_out;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use miette::{IntoDiagnostic, Result};
+	use twilight_model::id::Id;
+
+	use super::UserSettings;
+
+	#[test]
+	fn test_default_settings() -> Result<()> {
+		let settings: UserSettings = toml::from_str(r#"id = "123""#).into_diagnostic()?;
+
+		assert_eq!(settings.id().get(), 123);
+		assert!(settings.locale().is_none());
+		assert!(!settings.dm_opt_out());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_reload_settings() -> Result<()> {
+		let mut settings = UserSettings::new(Id::new(123));
+		settings.set_locale(Some("de".to_owned()));
+		settings.set_dm_opt_out(true);
+
+		let saved = toml::to_string(&settings).into_diagnostic()?;
+		let loaded: UserSettings = toml::from_str(&saved).into_diagnostic()?;
+
+		assert_eq!(loaded.locale(), Some("de"));
+		assert!(loaded.dm_opt_out());
+
+		Ok(())
+	}
+}
@@ -1,4 +1,4 @@
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
 use futures_util::Future;
 use reqwest::{header, Client};
@@ -14,7 +14,7 @@ use twilight_model::{
 use twilight_util::builder::command::{CommandBuilder, StringBuilder};
 
 use crate::{
-	helpers::{parsing::CommandParse, InteractionsHelper, STARLIGHT_COLORS},
+	helpers::{parsing::CommandOptions, InteractionsHelper, STARLIGHT_COLORS},
 	prelude::*,
 	slashies::{DefineCommand, SlashCommand, SlashData},
 };
@@ -130,6 +130,10 @@ impl Crate {
 }
 
 impl SlashCommand for Crate {
+	fn cooldown(&self) -> Option<Duration> {
+		Some(Duration::from_secs(5))
+	}
+
 	fn run(
 		&self,
 		helper: InteractionsHelper,
@@ -144,6 +148,8 @@ impl SlashCommand for Crate {
 				return Ok(());
 			}
 
+			helper.defer(&responder).await.into_diagnostic()?;
+
 			let cdn = helper.cdn();
 
 			let krate = self.get_crate(cdn).await?;
@@ -176,7 +182,7 @@ impl SlashCommand for Crate {
 				}
 			}
 
-			helper.respond(&mut responder).await.into_diagnostic()?;
+			helper.update(&mut responder).await?;
 
 			Ok(())
 		})
@@ -246,16 +252,10 @@ impl DefineCommand for Crate {
 	}
 
 	fn parse(data: CommandData) -> Result<Self> {
-		let name = data
-			.options
-			.into_iter()
-			.find(|value| value.name == "crate_name")
-			.ok_or_else(|| error!("Failed to find option"))?;
-
-		let crate_name = name
-			.value
-			.parse_option()
-			.ok_or_else(|| error!("value is not a string (this shouldn't happen)"))?;
+		let crate_name = data
+			.string_option("crate_name")
+			.ok_or_else(|| error!("Failed to find option"))?
+			.to_owned();
 
 		Ok(Self { crate_name })
 	}
@@ -0,0 +1,111 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+use twilight_model::{
+	application::{
+		command::{Command, CommandType},
+		interaction::application_command::CommandData,
+	},
+	channel::embed::Embed,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use crate::{
+	helpers::{InteractionsHelper, STARLIGHT_COLORS},
+	prelude::*,
+	slashies::{DefineCommand, Paginator, SlashCommand, SlashData},
+};
+
+const COMMANDS_PER_PAGE: usize = 10;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Help;
+
+impl Help {
+	fn embeds(commands: &[Command]) -> Result<Vec<Embed>> {
+		let pages = commands.chunks(COMMANDS_PER_PAGE).count();
+
+		commands
+			.chunks(COMMANDS_PER_PAGE)
+			.enumerate()
+			.map(|(page, chunk)| {
+				chunk
+					.iter()
+					.fold(
+						EmbedBuilder::new()
+							.color(STARLIGHT_COLORS[0].to_decimal())
+							.title("Commands")
+							.footer(EmbedFooterBuilder::new(format!(
+								"Page {} of {}",
+								page + 1,
+								pages
+							))),
+						|builder, command| {
+							builder.field(EmbedFieldBuilder::new(
+								format!("/{}", command.name),
+								command.description.as_str(),
+							))
+						},
+					)
+					.build()
+					.into_diagnostic()
+			})
+			.collect()
+	}
+}
+
+impl SlashCommand for Help {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			let commands = InteractionsHelper::get_slashies().map(|(command, _)| command);
+			let pages = Self::embeds(&commands)?;
+
+			Paginator::new(pages).run(helper, responder).await
+		})
+	}
+}
+
+impl DefineCommand for Help {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"help".to_owned(),
+			"Lists the available commands".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+	}
+
+	fn parse(_: CommandData) -> Result<Self> {
+		Ok(Self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Help;
+	use crate::{helpers::InteractionsHelper, prelude::*};
+
+	#[test]
+	fn test_lists_every_command() -> Result<()> {
+		let commands = InteractionsHelper::get_slashies().map(|(command, _)| command);
+		let embeds = Help::embeds(&commands)?;
+
+		for command in &commands {
+			let name = format!("/{}", command.name);
+
+			assert!(embeds
+				.iter()
+				.flat_map(|embed| &embed.fields)
+				.any(|field| field.name == name));
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,234 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_cache_inmemory::{InMemoryCache as Cache, ResourceType};
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+use twilight_model::{
+	application::{
+		command::CommandType,
+		interaction::application_command::{CommandData, CommandDataOption},
+	},
+	channel::embed::Embed,
+	id::{marker::UserMarker, Id},
+};
+use twilight_util::builder::command::{CommandBuilder, SubCommandBuilder, UserBuilder};
+
+use crate::{
+	helpers::{parsing::CommandOptions, InteractionsHelper, STARLIGHT_COLORS},
+	prelude::*,
+	slashies::{CommandScope, DefineCommand, Paginator, SlashCommand, SlashData, Subcommand},
+	utils::DefaultMessages,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Inspect {
+	User { user_id: Id<UserMarker> },
+}
+
+impl Inspect {
+	fn user_embeds(cache: &Cache, user_id: Id<UserMarker>) -> Result<Vec<Embed>> {
+		let user = match cache.user(user_id) {
+			Some(user) => user,
+			None => {
+				let embed = EmbedBuilder::new()
+					.color(STARLIGHT_COLORS[1].to_decimal())
+					.title("Nothing cached")
+					.description(format!("the cache holds no data for user `{}`", user_id))
+					.build()
+					.into_diagnostic()?;
+
+				return Ok(vec![embed]);
+			}
+		};
+
+		let guild_ids = cache
+			.user_guilds(user_id)
+			.map(|guilds| guilds.iter().copied().collect::<Vec<_>>())
+			.unwrap_or_default();
+
+		let mut embeds = Vec::with_capacity(guild_ids.len() + 1);
+
+		embeds.push(
+			EmbedBuilder::new()
+				.color(STARLIGHT_COLORS[0].to_decimal())
+				.title(user.name.as_str())
+				.field(EmbedFieldBuilder::new("ID", user_id.to_string()).inline())
+				.field(EmbedFieldBuilder::new("Bot", user.bot.to_string()).inline())
+				.field(
+					EmbedFieldBuilder::new("Mutual guilds", guild_ids.len().to_string()).inline(),
+				)
+				.build()
+				.into_diagnostic()?,
+		);
+
+		for guild_id in guild_ids {
+			let guild_name = cache
+				.guild(guild_id)
+				.map_or_else(|| guild_id.to_string(), |guild| guild.name().to_owned());
+
+			let mut builder = EmbedBuilder::new()
+				.color(STARLIGHT_COLORS[2].to_decimal())
+				.title(guild_name);
+
+			if let Some(member) = cache.member(guild_id, user_id) {
+				let roles = member
+					.roles()
+					.iter()
+					.map(|role_id| format!("<@&{}>", role_id))
+					.collect::<Vec<_>>();
+
+				builder = builder
+					.field(
+						EmbedFieldBuilder::new("Nickname", member.nick().unwrap_or("_<none>_"))
+							.inline(),
+					)
+					.field(
+						EmbedFieldBuilder::new(
+							"Timed out until",
+							member.communication_disabled_until().map_or_else(
+								|| "_<not timed out>_".to_owned(),
+								|t| t.iso_8601().to_string(),
+							),
+						)
+						.inline(),
+					)
+					.field(EmbedFieldBuilder::new(
+						"Roles",
+						if roles.is_empty() {
+							"_<none>_".to_owned()
+						} else {
+							roles.join(" ")
+						},
+					));
+			} else {
+				builder = builder.description("_<no member data cached>_");
+			}
+
+			embeds.push(builder.build().into_diagnostic()?);
+		}
+
+		Ok(embeds)
+	}
+}
+
+impl SlashCommand for Inspect {
+	fn cache_needs(&self) -> ResourceType {
+		ResourceType::USER | ResourceType::MEMBER | ResourceType::GUILD | ResourceType::ROLE
+	}
+
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			if helper.config().owner_id != Some(responder.user_id()) {
+				responder.error(DefaultMessages::PermissionDenied.to_string());
+				helper.respond(&mut responder).await.into_diagnostic()?;
+				return Ok(());
+			}
+
+			match self {
+				Self::User { user_id } => {
+					let pages = Self::user_embeds(helper.cache(), *user_id)?;
+
+					Paginator::new(pages).run(helper, responder).await
+				}
+			}
+		})
+	}
+}
+
+impl DefineCommand for Inspect {
+	const SCOPE: CommandScope = CommandScope::Guild;
+
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"inspect".to_owned(),
+			"Inspect cached data (owner only)".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(
+			SubCommandBuilder::new("user".to_owned(), "Inspect a cached user".to_owned()).option(
+				UserBuilder::new("id".to_owned(), "The user to inspect".to_owned()).required(true),
+			),
+		)
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		Self::parse_options(data.options)
+	}
+}
+
+impl Subcommand for Inspect {
+	fn parse_subcommand(name: &str, options: &[CommandDataOption]) -> Result<Self> {
+		match name {
+			"user" => {
+				let user_id = options
+					.user_option("id")
+					.ok_or_else(|| error!("value is not a user (this shouldn't happen)"))?;
+
+				Ok(Self::User { user_id })
+			}
+			_ => Err(error!("invalid subcommand variant")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+	use twilight_cache_inmemory::InMemoryCache;
+	use twilight_model::{channel::Message, gateway::payload::incoming::MessageCreate, id::Id};
+
+	use super::Inspect;
+	use crate::prelude::*;
+
+	#[test]
+	fn test_user_embeds() -> Result<()> {
+		let cache = InMemoryCache::new();
+		let message: Message = serde_json::from_value(json!({
+			"attachments": [],
+			"author": {
+				"avatar": null,
+				"bot": false,
+				"discriminator": "0001",
+				"id": "2",
+				"username": "star",
+			},
+			"channel_id": "1",
+			"content": "hi",
+			"edited_timestamp": null,
+			"embeds": [],
+			"guild_id": "5",
+			"id": "3",
+			"mention_everyone": false,
+			"mention_roles": [],
+			"mentions": [],
+			"pinned": false,
+			"timestamp": "2021-01-01T00:00:00.000000+00:00",
+			"tts": false,
+			"type": 0,
+		}))
+		.into_diagnostic()?;
+		cache.update(&MessageCreate(message));
+
+		let embeds = Inspect::user_embeds(&cache, Id::new(2))?;
+
+		assert_eq!(embeds[0].title.as_deref(), Some("star"));
+		assert!(embeds[0]
+			.fields
+			.iter()
+			.any(|field| field.name == "ID" && field.value == "2"));
+
+		let missing = Inspect::user_embeds(&cache, Id::new(4))?;
+
+		assert_eq!(missing.len(), 1);
+		assert_eq!(missing[0].title.as_deref(), Some("Nothing cached"));
+
+		Ok(())
+	}
+}
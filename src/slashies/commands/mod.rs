@@ -1,6 +1,14 @@
+mod help;
+mod inspect;
 #[path = "crate.rs"]
 mod krate;
 mod ping;
+mod preferences;
+mod settings;
+mod stats;
 mod tag;
 
-pub use self::{krate::Crate, ping::Ping, tag::Tag};
+pub use self::{
+	help::Help, inspect::Inspect, krate::Crate, ping::Ping, preferences::Preferences,
+	settings::Settings, stats::Stats, tag::Tag,
+};
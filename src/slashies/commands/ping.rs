@@ -33,11 +33,15 @@ impl SlashCommand for Ping {
 				.average()
 			{
 				data.message(format!(
-					"Pong! Average latency is {} milliseconds",
-					pong.as_millis()
+					"Pong! Average latency is {} milliseconds, up for {}",
+					pong.as_millis(),
+					context.uptime_human()
 				));
 			} else {
-				data.message("Pong! Couldn't quite get average latency".to_owned());
+				data.message(format!(
+					"Pong! Couldn't quite get average latency, up for {}",
+					context.uptime_human()
+				));
 			}
 
 			helper.respond(&mut data).await.into_diagnostic()?;
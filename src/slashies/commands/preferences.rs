@@ -0,0 +1,110 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::{BooleanBuilder, CommandBuilder, StringBuilder};
+
+use crate::{
+	helpers::{parsing::CommandOptions, InteractionsHelper},
+	prelude::*,
+	settings::{Tables, UserSettings},
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+// running it without options shows the current preferences.
+#[derive(Debug, Clone)]
+pub struct Preferences {
+	locale: Option<String>,
+	dms: Option<bool>,
+}
+
+impl Preferences {
+	fn apply(&self, settings: &mut UserSettings) {
+		if let Some(locale) = &self.locale {
+			settings.set_locale(Some(locale.clone()).filter(|locale| locale != "default"));
+		}
+
+		if let Some(dms) = self.dms {
+			settings.set_dm_opt_out(!dms);
+		}
+	}
+}
+
+impl SlashCommand for Preferences {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			let user_id = responder.user_id();
+			let database = helper.database();
+
+			// only writes when something changed, viewing the preferences never creates an entry.
+			let settings = if self.locale.is_some() || self.dms.is_some() {
+				let _guard = Tables::Users.lock(&user_id).await;
+
+				let existing = Tables::Users
+					.find_entry::<UserSettings>(database, &user_id)
+					.await?;
+				let exists = existing.is_some();
+
+				let mut settings = existing.unwrap_or_else(|| UserSettings::new(user_id));
+				self.apply(&mut settings);
+
+				if exists {
+					Tables::Users.update_entry(database, &settings).await?;
+				} else {
+					Tables::Users.create_entry(database, &settings).await?;
+				}
+
+				settings
+			} else {
+				Tables::Users
+					.find_entry(database, &user_id)
+					.await?
+					.unwrap_or_else(|| UserSettings::new(user_id))
+			};
+
+			responder.message(format!(
+				"locale: `{}`\ndirect messages: {}",
+				settings.locale().unwrap_or("default"),
+				if settings.dm_opt_out() { "off" } else { "on" }
+			));
+
+			helper.respond(&mut responder).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Preferences {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"preferences".to_owned(),
+			"Shows or changes your preferences, in every guild".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(StringBuilder::new(
+			"locale".to_owned(),
+			"Locale to respond in, or `default` to follow the guild".to_owned(),
+		))
+		.option(BooleanBuilder::new(
+			"dms".to_owned(),
+			"Whether the bot may send you direct messages".to_owned(),
+		))
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		Ok(Self {
+			locale: data.string_option("locale").map(ToOwned::to_owned),
+			dms: data.boolean_option("dms"),
+		})
+	}
+}
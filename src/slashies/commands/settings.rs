@@ -0,0 +1,472 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_cache_inmemory::ResourceType;
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+use twilight_model::{
+	application::{
+		command::CommandType,
+		interaction::application_command::{CommandData, CommandDataOption},
+	},
+	channel::{embed::Embed, ChannelType},
+	guild::Permissions,
+	id::{
+		marker::{ChannelMarker, UserMarker},
+		Id,
+	},
+};
+use twilight_util::builder::command::{
+	ChannelBuilder, CommandBuilder, StringBuilder, SubCommandBuilder, UserBuilder,
+};
+
+use crate::{
+	helpers::{parsing::CommandOptions, InteractionsHelper, STARLIGHT_COLORS},
+	prelude::*,
+	settings::{GuildSettings, Tables},
+	slashies::{DefineCommand, SlashCommand, SlashData, Subcommand},
+	utils::DefaultMessages,
+};
+
+const MAX_LOCALE_LENGTH: usize = 10;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsError {
+	#[error("that locale doesn't look right, use a tag such as `en-US` or `de`")]
+	InvalidLocale,
+	#[error("the log channel has to be a channel in this guild")]
+	ChannelNotInGuild,
+}
+
+// omitting the option of a subcommand resets that setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Settings {
+	Show,
+	Locale {
+		locale: Option<String>,
+	},
+	LogChannel {
+		channel_id: Option<Id<ChannelMarker>>,
+	},
+	Block {
+		user_id: Id<UserMarker>,
+		reason: Option<String>,
+	},
+	Unblock {
+		user_id: Id<UserMarker>,
+	},
+}
+
+impl Settings {
+	fn validate(&self, guild_channels: &[Id<ChannelMarker>]) -> Result<(), SettingsError> {
+		match self {
+			Self::Locale {
+				locale: Some(locale),
+			} if locale.is_empty()
+				|| locale.len() > MAX_LOCALE_LENGTH
+				|| !locale
+					.chars()
+					.all(|c| c.is_ascii_alphanumeric() || c == '-') =>
+			{
+				Err(SettingsError::InvalidLocale)
+			}
+			Self::LogChannel {
+				channel_id: Some(channel_id),
+			} if !guild_channels.contains(channel_id) => Err(SettingsError::ChannelNotInGuild),
+			_ => Ok(()),
+		}
+	}
+
+	// returns the confirmation shown to the user.
+	fn apply(&self, settings: &mut GuildSettings) -> String {
+		match self {
+			Self::Show => "nothing was changed".to_owned(),
+			Self::Locale { locale } => {
+				settings.set_locale(locale.clone());
+
+				locale.as_ref().map_or_else(
+					|| "the locale was reset".to_owned(),
+					|locale| format!("the locale is now `{}`", locale),
+				)
+			}
+			Self::LogChannel { channel_id } => {
+				settings.set_log_channel(*channel_id);
+
+				channel_id.map_or_else(
+					|| "logging is now disabled".to_owned(),
+					|channel_id| format!("logs are now sent to <#{}>", channel_id),
+				)
+			}
+			Self::Block { user_id, reason } => {
+				if settings.block_user(*user_id, reason.clone()) {
+					format!("<@{}> can no longer use commands here", user_id)
+				} else {
+					format!("<@{}> is already blocked", user_id)
+				}
+			}
+			Self::Unblock { user_id } => {
+				if settings.unblock_user(*user_id) {
+					format!("<@{}> can use commands here again", user_id)
+				} else {
+					format!("<@{}> isn't blocked", user_id)
+				}
+			}
+		}
+	}
+
+	fn embed(settings: &GuildSettings) -> Result<Embed> {
+		let fields = [
+			(
+				"Locale",
+				settings.locale().map_or_else(
+					|| "_<default>_".to_owned(),
+					|locale| format!("`{}`", locale),
+				),
+			),
+			(
+				"Log channel",
+				settings.log_channel().map_or_else(
+					|| "_<none>_".to_owned(),
+					|channel_id| format!("<#{}>", channel_id),
+				),
+			),
+			("Tags", settings.tags().len().to_string()),
+			("Blocked users", settings.blocked_users().len().to_string()),
+		];
+
+		fields
+			.into_iter()
+			.fold(
+				EmbedBuilder::new()
+					.color(STARLIGHT_COLORS[0].to_decimal())
+					.title("Settings"),
+				|builder, (name, value)| {
+					builder.field(EmbedFieldBuilder::new(name, value).inline())
+				},
+			)
+			.build()
+			.into_diagnostic()
+	}
+
+	fn parse_locale(options: &[CommandDataOption]) -> Self {
+		Self::Locale {
+			locale: options.string_option("locale").map(ToOwned::to_owned),
+		}
+	}
+
+	fn parse_log_channel(options: &[CommandDataOption]) -> Self {
+		Self::LogChannel {
+			channel_id: options.channel_option("channel"),
+		}
+	}
+
+	fn parse_user(options: &[CommandDataOption]) -> Result<Id<UserMarker>> {
+		options
+			.user_option("user")
+			.ok_or_else(|| error!("value is not a user (this shouldn't happen)"))
+	}
+}
+
+impl SlashCommand for Settings {
+	fn cache_needs(&self) -> ResourceType {
+		match self {
+			Self::LogChannel { .. } => ResourceType::CHANNEL,
+			_ => ResourceType::empty(),
+		}
+	}
+
+	fn required_permissions(&self) -> Permissions {
+		match self {
+			Self::Show => Permissions::empty(),
+			_ => Permissions::MANAGE_GUILD,
+		}
+	}
+
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+		Box::pin(async move {
+			responder.ephemeral();
+
+			let guild_id = if let Some(guild_id) = responder.guild_id {
+				guild_id
+			} else {
+				responder.error(DefaultMessages::GuildOnly.to_string());
+				helper.respond(&mut responder).await.into_diagnostic()?;
+				return Ok(());
+			};
+
+			let guild_channels = helper
+				.cache()
+				.guild_channels(guild_id)
+				.map(|channels| channels.iter().copied().collect::<Vec<_>>())
+				.unwrap_or_default();
+
+			if *self == Self::Show {
+				let settings = Tables::Guilds
+					.get_entry::<GuildSettings>(helper.database(), &guild_id)
+					.await?;
+
+				responder.embed(Self::embed(&settings)?);
+				helper.respond(&mut responder).await.into_diagnostic()?;
+
+				return Ok(());
+			}
+
+			let message = match self.validate(&guild_channels) {
+				Ok(()) => {
+					Tables::Guilds
+						.modify_entry::<GuildSettings, _, _>(
+							helper.database(),
+							&guild_id,
+							|settings| self.apply(settings),
+						)
+						.await?
+				}
+				Err(e) => e.to_string(),
+			};
+
+			responder.message(message);
+			helper.respond(&mut responder).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Settings {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"settings".to_owned(),
+			"Shows or changes this guild's settings".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+		.option(SubCommandBuilder::new(
+			"show".to_owned(),
+			"Show the current settings".to_owned(),
+		))
+		.option(
+			SubCommandBuilder::new(
+				"locale".to_owned(),
+				"Set the locale commands respond in".to_owned(),
+			)
+			.option(StringBuilder::new(
+				"locale".to_owned(),
+				"Locale such as `en-US`, leave empty to reset".to_owned(),
+			)),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"log-channel".to_owned(),
+				"Set the channel the bot logs to".to_owned(),
+			)
+			.option(
+				ChannelBuilder::new(
+					"channel".to_owned(),
+					"Channel to log to, leave empty to disable logging".to_owned(),
+				)
+				.channel_types([ChannelType::GuildText]),
+			),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"block".to_owned(),
+				"Stop a user from using commands in this guild".to_owned(),
+			)
+			.option(
+				UserBuilder::new("user".to_owned(), "The user to block".to_owned()).required(true),
+			)
+			.option(StringBuilder::new(
+				"reason".to_owned(),
+				"Why the user is blocked".to_owned(),
+			)),
+		)
+		.option(
+			SubCommandBuilder::new(
+				"unblock".to_owned(),
+				"Let a blocked user use commands again".to_owned(),
+			)
+			.option(
+				UserBuilder::new("user".to_owned(), "The user to unblock".to_owned())
+					.required(true),
+			),
+		)
+	}
+
+	fn parse(data: CommandData) -> Result<Self> {
+		Self::parse_options(data.options)
+	}
+}
+
+impl Subcommand for Settings {
+	fn parse_subcommand(name: &str, options: &[CommandDataOption]) -> Result<Self> {
+		match name {
+			"show" => Ok(Self::Show),
+			"locale" => Ok(Self::parse_locale(options)),
+			"log-channel" => Ok(Self::parse_log_channel(options)),
+			"block" => Ok(Self::Block {
+				user_id: Self::parse_user(options)?,
+				reason: options.string_option("reason").map(ToOwned::to_owned),
+			}),
+			"unblock" => Ok(Self::Unblock {
+				user_id: Self::parse_user(options)?,
+			}),
+			_ => Err(error!("invalid subcommand variant")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::id::Id;
+
+	use super::{Settings, SettingsError};
+	use crate::{
+		prelude::*,
+		settings::GuildSettings,
+		slashies::{testing::CommandDataBuilder, DefineCommand},
+	};
+
+	#[test]
+	fn test_set_fields() {
+		let mut settings = GuildSettings::new(Id::new(1));
+
+		Settings::Locale {
+			locale: Some("de".to_owned()),
+		}
+		.apply(&mut settings);
+		assert_eq!(settings.locale(), Some("de"));
+
+		Settings::LogChannel {
+			channel_id: Some(Id::new(2)),
+		}
+		.apply(&mut settings);
+		assert_eq!(settings.log_channel(), Some(Id::new(2)));
+
+		Settings::Locale { locale: None }.apply(&mut settings);
+		Settings::LogChannel { channel_id: None }.apply(&mut settings);
+		assert!(settings.locale().is_none());
+		assert!(settings.log_channel().is_none());
+	}
+
+	#[test]
+	fn test_block_users() {
+		let mut settings = GuildSettings::new(Id::new(1));
+		let block = Settings::Block {
+			user_id: Id::new(3),
+			reason: Some("spam".to_owned()),
+		};
+
+		assert_eq!(
+			block.apply(&mut settings),
+			"<@3> can no longer use commands here"
+		);
+		assert_eq!(block.apply(&mut settings), "<@3> is already blocked");
+		assert!(settings.is_blocked(Id::new(3)));
+		assert_eq!(settings.blocked_users()[0].reason(), Some("spam"));
+
+		let unblock = Settings::Unblock {
+			user_id: Id::new(3),
+		};
+
+		assert_eq!(
+			unblock.apply(&mut settings),
+			"<@3> can use commands here again"
+		);
+		assert_eq!(unblock.apply(&mut settings), "<@3> isn't blocked");
+		assert!(!settings.is_blocked(Id::new(3)));
+	}
+
+	#[test]
+	fn test_embed_fields() -> Result<()> {
+		let mut settings = GuildSettings::new(Id::new(1));
+		settings.set_log_channel(Some(Id::new(2)));
+
+		let embed = Settings::embed(&settings)?;
+		let names = embed
+			.fields
+			.iter()
+			.map(|field| field.name.as_str())
+			.collect::<Vec<_>>();
+
+		assert_eq!(names, ["Locale", "Log channel", "Tags", "Blocked users"]);
+		assert_eq!(embed.fields[0].value, "_<default>_");
+		assert_eq!(embed.fields[1].value, "<#2>");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse() -> Result<()> {
+		let data = CommandDataBuilder::new("settings")
+			.subcommand("log-channel", |options| {
+				options.channel("channel", Id::new(2))
+			})
+			.build();
+
+		assert_eq!(
+			Settings::parse(data)?,
+			Settings::LogChannel {
+				channel_id: Some(Id::new(2))
+			}
+		);
+
+		let data = CommandDataBuilder::new("settings")
+			.subcommand("locale", |options| options)
+			.build();
+
+		assert_eq!(Settings::parse(data)?, Settings::Locale { locale: None });
+
+		let data = CommandDataBuilder::new("settings")
+			.subcommand("block", |options| options.user("user", Id::new(3)))
+			.build();
+
+		assert_eq!(
+			Settings::parse(data)?,
+			Settings::Block {
+				user_id: Id::new(3),
+				reason: None
+			}
+		);
+
+		let data = CommandDataBuilder::new("settings")
+			.subcommand("unblock", |options| options)
+			.build();
+
+		assert!(Settings::parse(data).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_validate() {
+		let channels = [Id::new(2), Id::new(3)];
+
+		assert_eq!(
+			Settings::LogChannel {
+				channel_id: Some(Id::new(4))
+			}
+			.validate(&channels),
+			Err(SettingsError::ChannelNotInGuild)
+		);
+		assert!(Settings::LogChannel {
+			channel_id: Some(Id::new(3))
+		}
+		.validate(&channels)
+		.is_ok());
+		assert_eq!(
+			Settings::Locale {
+				locale: Some("not a locale".to_owned())
+			}
+			.validate(&channels),
+			Err(SettingsError::InvalidLocale)
+		);
+		assert!(Settings::Locale {
+			locale: Some("en-US".to_owned())
+		}
+		.validate(&channels)
+		.is_ok());
+	}
+}
@@ -0,0 +1,83 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_embed_builder::{EmbedBuilder, EmbedFieldBuilder};
+use twilight_model::application::{
+	command::CommandType, interaction::application_command::CommandData,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use crate::{
+	helpers::{InteractionsHelper, STARLIGHT_COLORS},
+	prelude::*,
+	slashies::{DefineCommand, SlashCommand, SlashData},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats;
+
+impl SlashCommand for Stats {
+	fn run(
+		&self,
+		helper: InteractionsHelper,
+		mut responder: SlashData,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+		Box::pin(async move {
+			let context = helper.context();
+			let stats = context.cache_stats();
+
+			let fields = [
+				("Uptime", context.uptime_human()),
+				("Guilds", stats.guilds.to_string()),
+				("Users", stats.users.to_string()),
+				("Members", stats.members.to_string()),
+				("Channels", stats.channels.to_string()),
+				("Roles", stats.roles.to_string()),
+				("Emojis", stats.emojis.to_string()),
+				(
+					"Shards",
+					context
+						.shard_statuses()
+						.into_iter()
+						.map(|(id, status)| format!("{}: {}", id, status))
+						.collect::<Vec<_>>()
+						.join("\n"),
+				),
+			];
+
+			let embed = fields
+				.into_iter()
+				.fold(
+					EmbedBuilder::new()
+						.color(STARLIGHT_COLORS[0].to_decimal())
+						.title("Stats"),
+					|builder, (name, value)| {
+						builder.field(EmbedFieldBuilder::new(name, value).inline())
+					},
+				)
+				.build()
+				.into_diagnostic()?;
+
+			responder.embed(embed);
+
+			helper.respond(&mut responder).await.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+}
+
+impl DefineCommand for Stats {
+	fn define() -> CommandBuilder {
+		CommandBuilder::new(
+			"stats".to_owned(),
+			"Shows the bot's uptime and cache statistics".to_owned(),
+			CommandType::ChatInput,
+		)
+		.default_permission(true)
+	}
+
+	fn parse(_: CommandData) -> Result<Self> {
+		Ok(Self)
+	}
+}
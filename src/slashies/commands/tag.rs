@@ -1,20 +1,21 @@
-use std::{hint::unreachable_unchecked, pin::Pin};
+use std::{pin::Pin, time::Duration};
 
 use futures_util::{Future, FutureExt};
+use starlight_macros::unexpected;
 use twilight_model::{
 	application::{
 		command::{CommandOptionChoice, CommandType},
-		interaction::application_command::{CommandData, CommandDataOption, CommandOptionValue},
+		interaction::application_command::{CommandData, CommandDataOption},
 	},
 	guild::Permissions,
 };
 use twilight_util::builder::command::{CommandBuilder, StringBuilder, SubCommandBuilder};
 
 use crate::{
-	helpers::{parsing::CommandParse, InteractionsHelper},
+	helpers::{parsing::CommandOptions, InteractionsHelper},
 	prelude::*,
 	settings::{GuildSettings, GuildTag, Tables},
-	slashies::{DefineCommand, SlashCommand, SlashData},
+	slashies::{DefineCommand, SlashCommand, SlashData, Subcommand},
 	utils::{levenshtein, DefaultMessages},
 };
 
@@ -28,18 +29,8 @@ pub enum Tag {
 
 impl Tag {
 	fn parse_full(data: &[CommandDataOption]) -> (String, String) {
-		let name = data
-			.iter()
-			.find(|opt| opt.name == "name")
-			.cloned()
-			.and_then(|opt| opt.value.parse_option())
-			.unwrap_or_default();
-		let content = data
-			.iter()
-			.find(|opt| opt.name == "content")
-			.cloned()
-			.and_then(|opt| opt.value.parse_option())
-			.unwrap_or_default();
+		let name = data.string_option("name").unwrap_or_default().to_owned();
+		let content = data.string_option("content").unwrap_or_default().to_owned();
 
 		(name, content)
 	}
@@ -55,14 +46,7 @@ impl Tag {
 	}
 
 	fn parse_name(data: &[CommandDataOption]) -> String {
-		let name = data
-			.iter()
-			.find(|opt| opt.name == "name")
-			.cloned()
-			.and_then(|opt| opt.value.parse_option())
-			.unwrap_or_default();
-
-		name
+		data.string_option("name").unwrap_or_default().to_owned()
 	}
 
 	fn parse_delete(data: &[CommandDataOption]) -> Self {
@@ -80,20 +64,25 @@ impl Tag {
 	async fn run_show(self, helper: InteractionsHelper, mut responder: SlashData) -> Result<()> {
 		if let Self::Show { name } = self {
 			let guild_settings = Tables::Guilds
-				.get_entry::<GuildSettings>(helper.database(), unsafe {
-					&responder.guild_id.unwrap_unchecked()
-				})
+				.get_entry::<GuildSettings>(
+					helper.database(),
+					&responder
+						.guild_id
+						.ok_or_else(|| unexpected!("tag command outside a guild"))?,
+				)
 				.await?;
 
 			if let Some(tag) = guild_settings.tags().iter().find(|tag| tag.name() == name) {
 				responder.message(tag.description().to_owned());
 				helper.respond(&mut responder).await.into_diagnostic()?;
 			} else {
-				responder.message(format!("couldn't find tag `{}`", name));
+				responder
+					.message(format!("couldn't find tag `{}`", name))
+					.ephemeral();
 				helper.respond(&mut responder).await.into_diagnostic()?;
 			}
 		} else {
-			unsafe { unreachable_unchecked() }
+			return Err(unexpected!("`run_show` ran for another variant"));
 		}
 
 		Ok(())
@@ -101,17 +90,22 @@ impl Tag {
 
 	async fn run_add(self, helper: InteractionsHelper, mut responder: SlashData) -> Result<()> {
 		if let Self::Add { name, content } = self {
+			let guild_id = responder
+				.guild_id
+				.ok_or_else(|| unexpected!("tag command outside a guild"))?;
+			let _guard = Tables::Guilds.lock(&guild_id).await;
+
 			let mut guild_settings = Tables::Guilds
-				.get_entry::<GuildSettings>(helper.database(), unsafe {
-					&responder.guild_id.unwrap_unchecked()
-				})
+				.get_entry::<GuildSettings>(helper.database(), &guild_id)
 				.await?;
 
 			if guild_settings.tags().iter().any(|tag| tag.name() == name) {
-				responder.message(format!(
-					"the guild tag `{}` already exists, try editing or deleting it first.",
-					&name
-				));
+				responder
+					.message(format!(
+						"the guild tag `{}` already exists, try editing or deleting it first.",
+						&name
+					))
+					.ephemeral();
 				helper.respond(&mut responder).await.into_diagnostic()?;
 				return Ok(());
 			}
@@ -127,7 +121,7 @@ impl Tag {
 
 			helper.respond(&mut responder).await.into_diagnostic()?;
 		} else {
-			unsafe { unreachable_unchecked() }
+			return Err(unexpected!("`run_add` ran for another variant"));
 		}
 
 		Ok(())
@@ -135,10 +129,13 @@ impl Tag {
 
 	async fn run_edit(self, helper: InteractionsHelper, mut responder: SlashData) -> Result<()> {
 		if let Self::Edit { name, content } = self {
+			let guild_id = responder
+				.guild_id
+				.ok_or_else(|| unexpected!("tag command outside a guild"))?;
+			let _guard = Tables::Guilds.lock(&guild_id).await;
+
 			let mut guild_settings = Tables::Guilds
-				.get_entry::<GuildSettings>(helper.database(), unsafe {
-					&responder.guild_id.unwrap_unchecked()
-				})
+				.get_entry::<GuildSettings>(helper.database(), &guild_id)
 				.await?;
 
 			let can_manage_messages = {
@@ -156,7 +153,9 @@ impl Tag {
 				.find(|tag| tag.name() == name)
 			{
 				if !can_manage_messages && tag.author() != user_id {
-					responder.message(DefaultMessages::PermissionDenied.to_string());
+					responder
+						.message(DefaultMessages::PermissionDenied.to_string())
+						.ephemeral();
 					helper.respond(&mut responder).await.into_diagnostic()?;
 					return Ok(());
 				}
@@ -169,15 +168,17 @@ impl Tag {
 				responder.message(format!("successfully edited `{}`", name));
 				helper.respond(&mut responder).await.into_diagnostic()?;
 			} else {
-				responder.message(format!(
-					"the guild tag `{}` doesn't exist, try creating it first.",
-					name
-				));
+				responder
+					.message(format!(
+						"the guild tag `{}` doesn't exist, try creating it first.",
+						name
+					))
+					.ephemeral();
 
 				helper.respond(&mut responder).await.into_diagnostic()?;
 			}
 		} else {
-			unsafe { unreachable_unchecked() }
+			return Err(unexpected!("`run_edit` ran for another variant"));
 		}
 
 		Ok(())
@@ -185,10 +186,13 @@ impl Tag {
 
 	async fn run_delete(self, helper: InteractionsHelper, mut responder: SlashData) -> Result<()> {
 		if let Self::Delete { name } = self {
+			let guild_id = responder
+				.guild_id
+				.ok_or_else(|| unexpected!("tag command outside a guild"))?;
+			let _guard = Tables::Guilds.lock(&guild_id).await;
+
 			let mut guild_settings = Tables::Guilds
-				.get_entry::<GuildSettings>(helper.database(), unsafe {
-					&responder.guild_id.unwrap_unchecked()
-				})
+				.get_entry::<GuildSettings>(helper.database(), &guild_id)
 				.await?;
 
 			let can_manage_messages = {
@@ -207,13 +211,17 @@ impl Tag {
 				.map(GuildTag::name);
 
 			if !can_manage_messages && !manageable_tags.any(|tag| tag == name) {
-				responder.message(DefaultMessages::PermissionDenied.to_string());
+				responder
+					.message(DefaultMessages::PermissionDenied.to_string())
+					.ephemeral();
 				helper.respond(&mut responder).await.into_diagnostic()?;
 				return Ok(());
 			}
 
 			if guild_settings.remove_tag(&name).is_none() {
-				responder.message(format!("tag `{}` was not found.", &name));
+				responder
+					.message(format!("tag `{}` was not found.", &name))
+					.ephemeral();
 				helper.respond(&mut responder).await.into_diagnostic()?;
 				return Ok(());
 			}
@@ -225,7 +233,7 @@ impl Tag {
 			responder.message(format!("tag `{}` was successfully deleted.", &name));
 			helper.respond(&mut responder).await.into_diagnostic()?;
 		} else {
-			unsafe { unreachable_unchecked() }
+			return Err(unexpected!("`run_delete` ran for another variant"));
 		}
 
 		Ok(())
@@ -233,6 +241,10 @@ impl Tag {
 }
 
 impl SlashCommand for Tag {
+	fn cooldown(&self) -> Option<Duration> {
+		Some(Duration::from_secs(3))
+	}
+
 	fn run(
 		&self,
 		helper: InteractionsHelper,
@@ -240,7 +252,9 @@ impl SlashCommand for Tag {
 	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
 		async move {
 			if responder.is_dm() {
-				responder.message("this command can only be used in a guild".to_owned());
+				responder
+					.message("this command can only be used in a guild".to_owned())
+					.ephemeral();
 				helper.respond(&mut responder).await.into_diagnostic()?;
 				return Ok(());
 			}
@@ -264,7 +278,7 @@ impl SlashCommand for Tag {
 				return Ok(());
 			}
 			let name = match self {
-				Self::Add { .. } => unsafe { unreachable_unchecked() },
+				Self::Add { .. } => return Err(unexpected!("autocomplete ran for `tag add`")),
 				Self::Edit { name, .. } | Self::Delete { name, .. } | Self::Show { name } => {
 					name.as_str()
 				}
@@ -275,9 +289,12 @@ impl SlashCommand for Tag {
 			}
 
 			let mut guild_settings = Tables::Guilds
-				.get_entry::<GuildSettings>(helper.database(), unsafe {
-					&responder.guild_id.unwrap_unchecked()
-				})
+				.get_entry::<GuildSettings>(
+					helper.database(),
+					&responder
+						.guild_id
+						.ok_or_else(|| unexpected!("tag command outside a guild"))?,
+				)
 				.await?;
 
 			let tags = guild_settings.tags_mut();
@@ -370,25 +387,19 @@ impl DefineCommand for Tag {
 		)
 	}
 
-	fn parse(mut data: CommandData) -> Result<Self> {
-		if data.options.len() != 1 {
-			return Err(error!(
-				"more than one subcommand was received (this shouldn't happen)"
-			));
-		}
-		let subcommand_value = data
-			.options
-			.pop()
-			.ok_or_else(|| error!("failed to get subcommand value (this shouldn't happen)"))?;
-		match subcommand_value.value {
-			CommandOptionValue::SubCommand(v) => match subcommand_value.name.as_str() {
-				"add" => Ok(Self::parse_add(&v)),
-				"delete" => Ok(Self::parse_delete(&v)),
-				"edit" => Ok(Self::parse_edit(&v)),
-				"show" => Ok(Self::parse_show(&v)),
-				_ => Err(error!("invalid subcommand variant")),
-			},
-			_ => Err(error!("invalid subcommand value option")),
+	fn parse(data: CommandData) -> Result<Self> {
+		Self::parse_options(data.options)
+	}
+}
+
+impl Subcommand for Tag {
+	fn parse_subcommand(name: &str, options: &[CommandDataOption]) -> Result<Self> {
+		match name {
+			"add" => Ok(Self::parse_add(options)),
+			"delete" => Ok(Self::parse_delete(options)),
+			"edit" => Ok(Self::parse_edit(options)),
+			"show" => Ok(Self::parse_show(options)),
+			_ => Err(error!("invalid subcommand variant")),
 		}
 	}
 }
@@ -0,0 +1,201 @@
+use std::pin::Pin;
+
+use futures_util::Future;
+use twilight_model::application::interaction::MessageComponentInteraction;
+
+use crate::{helpers::InteractionsHelper, prelude::*};
+
+pub const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+const SEPARATOR: char = ':';
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum CustomIdError {
+	#[error("a custom id part can't be empty or contain `:`")]
+	InvalidPart,
+	#[error("custom ids can be at most 100 characters long, this one is {0}")]
+	TooLong(usize),
+}
+
+// state carried in a component's custom id, encoded as `namespace:part:part`. the namespace is
+// what the handler is registered under, so `CustomId::new("roles").part("add")` is handled by the
+// handler registered for `roles:`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomId {
+	namespace: String,
+	parts: Vec<String>,
+}
+
+impl CustomId {
+	#[must_use]
+	pub fn new(namespace: impl Into<String>) -> Self {
+		Self {
+			namespace: namespace.into(),
+			parts: Vec::new(),
+		}
+	}
+
+	#[must_use]
+	pub fn part(mut self, part: impl Display) -> Self {
+		self.parts.push(part.to_string());
+
+		self
+	}
+
+	#[must_use]
+	pub fn namespace(&self) -> &str {
+		&self.namespace
+	}
+
+	#[must_use]
+	pub fn parts(&self) -> &[String] {
+		&self.parts
+	}
+
+	pub fn encode(&self) -> Result<String, CustomIdError> {
+		let valid = |part: &String| !part.is_empty() && !part.contains(SEPARATOR);
+
+		if !valid(&self.namespace) || !self.parts.iter().all(valid) {
+			return Err(CustomIdError::InvalidPart);
+		}
+
+		let encoded = std::iter::once(&self.namespace)
+			.chain(&self.parts)
+			.map(String::as_str)
+			.collect::<Vec<_>>()
+			.join(":");
+
+		match encoded.chars().count() {
+			length if length > MAX_CUSTOM_ID_LENGTH => Err(CustomIdError::TooLong(length)),
+			_ => Ok(encoded),
+		}
+	}
+
+	pub fn decode(custom_id: &str) -> Result<Self, CustomIdError> {
+		let length = custom_id.chars().count();
+		if length > MAX_CUSTOM_ID_LENGTH {
+			return Err(CustomIdError::TooLong(length));
+		}
+
+		let mut split = custom_id.split(SEPARATOR).map(ToOwned::to_owned);
+		let namespace = split.next().unwrap_or_default();
+		let parts = split.collect::<Vec<_>>();
+
+		if namespace.is_empty() || parts.iter().any(String::is_empty) {
+			return Err(CustomIdError::InvalidPart);
+		}
+
+		Ok(Self { namespace, parts })
+	}
+}
+
+pub type ComponentHandler = fn(
+	InteractionsHelper,
+	MessageComponentInteraction,
+) -> Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+// handlers for components that outlive a single command run, such as persistent role menus.
+// components awaited through `Standby` are only registered to handle the ones left behind by a
+// restart, and their handlers must leave the live ones alone (see `Paginator::expired`).
+#[derive(Debug, Default, Clone)]
+#[must_use = "a ComponentRegistry does nothing if not used"]
+pub struct ComponentRegistry(Vec<(&'static str, ComponentHandler)>);
+
+impl ComponentRegistry {
+	pub const fn new() -> Self {
+		Self(Vec::new())
+	}
+
+	pub fn register(mut self, prefix: &'static str, handler: ComponentHandler) -> Self {
+		self.0.push((prefix, handler));
+
+		self
+	}
+
+	// the longest matching prefix wins, so `roles:color:` can be handled apart from `roles:`.
+	#[must_use]
+	pub fn find(&self, custom_id: &str) -> Option<ComponentHandler> {
+		self.0
+			.iter()
+			.filter(|(prefix, _)| custom_id.starts_with(prefix))
+			.max_by_key(|(prefix, _)| prefix.len())
+			.map(|(_, handler)| *handler)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::pin::Pin;
+
+	use futures_util::Future;
+	use twilight_model::application::interaction::MessageComponentInteraction;
+
+	use super::{ComponentRegistry, CustomId, CustomIdError, MAX_CUSTOM_ID_LENGTH};
+	use crate::{helpers::InteractionsHelper, prelude::*};
+
+	fn roles(
+		_: InteractionsHelper,
+		_: MessageComponentInteraction,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+		Box::pin(async { Ok(()) })
+	}
+
+	fn colors(
+		_: InteractionsHelper,
+		_: MessageComponentInteraction,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+		Box::pin(async { Err(error!("colors")) })
+	}
+
+	#[test]
+	fn test_find_handler() {
+		let registry = ComponentRegistry::new()
+			.register("roles:", roles)
+			.register("roles:color:", colors);
+
+		assert_eq!(
+			registry.find("roles:123").map(|handler| handler as usize),
+			Some(roles as usize)
+		);
+		assert_eq!(
+			registry
+				.find("roles:color:red")
+				.map(|handler| handler as usize),
+			Some(colors as usize)
+		);
+		assert!(registry.find("paginator:next").is_none());
+	}
+
+	#[test]
+	fn test_custom_id_round_trip() -> Result<()> {
+		let custom_id = CustomId::new("roles").part("add").part(1234);
+		let encoded = custom_id.encode().into_diagnostic()?;
+
+		assert_eq!(encoded, "roles:add:1234");
+		assert_eq!(CustomId::decode(&encoded).into_diagnostic()?, custom_id);
+		assert!(CustomId::decode("paginator")
+			.into_diagnostic()?
+			.parts()
+			.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_invalid_custom_id() {
+		assert_eq!(
+			CustomId::new("roles").part("a:b").encode(),
+			Err(CustomIdError::InvalidPart)
+		);
+		assert_eq!(
+			CustomId::decode("roles::1"),
+			Err(CustomIdError::InvalidPart)
+		);
+		assert_eq!(
+			CustomId::new("roles")
+				.part("x".repeat(MAX_CUSTOM_ID_LENGTH))
+				.encode(),
+			Err(CustomIdError::TooLong(MAX_CUSTOM_ID_LENGTH + 6))
+		);
+	}
+}
@@ -0,0 +1,68 @@
+use std::{
+	collections::BTreeMap,
+	sync::{Mutex, PoisonError},
+	time::{Duration, Instant},
+};
+
+use twilight_model::id::{marker::UserMarker, Id};
+
+// keyed by command name and invoking user, storing when the cooldown expires.
+#[derive(Debug, Default)]
+pub struct Cooldowns(Mutex<BTreeMap<(String, Id<UserMarker>), Instant>>);
+
+impl Cooldowns {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(Mutex::new(BTreeMap::new()))
+	}
+
+	// returns the time remaining if the user is still on cooldown, otherwise starts a new cooldown.
+	pub fn check(
+		&self,
+		command_name: &str,
+		user_id: Id<UserMarker>,
+		cooldown: Duration,
+		now: Instant,
+	) -> Result<(), Duration> {
+		let mut cooldowns = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+		if let Some(expires) = cooldowns.get(&(command_name.to_owned(), user_id)) {
+			if *expires > now {
+				return Err(*expires - now);
+			}
+		}
+
+		cooldowns.retain(|_, expires| *expires > now);
+		cooldowns.insert((command_name.to_owned(), user_id), now + cooldown);
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use twilight_model::id::Id;
+
+	use super::Cooldowns;
+
+	#[test]
+	fn test_rapid_invocations() {
+		let cooldowns = Cooldowns::new();
+		let cooldown = Duration::from_secs(5);
+		let now = Instant::now();
+		let user_id = Id::new(1);
+
+		assert!(cooldowns.check("crate", user_id, cooldown, now).is_ok());
+		assert_eq!(
+			cooldowns.check("crate", user_id, cooldown, now + Duration::from_secs(2)),
+			Err(Duration::from_secs(3))
+		);
+		assert!(cooldowns.check("tag", user_id, cooldown, now).is_ok());
+		assert!(cooldowns.check("crate", Id::new(2), cooldown, now).is_ok());
+		assert!(cooldowns
+			.check("crate", user_id, cooldown, now + cooldown)
+			.is_ok());
+	}
+}
@@ -1,7 +1,13 @@
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
 use futures_util::Future;
-use twilight_model::application::interaction::application_command::CommandData;
+use twilight_cache_inmemory::ResourceType;
+use twilight_model::{
+	application::interaction::application_command::{
+		CommandData, CommandDataOption, CommandOptionValue,
+	},
+	guild::Permissions,
+};
 use twilight_util::builder::command::CommandBuilder;
 
 use super::SlashData;
@@ -14,6 +20,21 @@ pub trait SlashCommand: Send + Sync {
 		responder: SlashData,
 	) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
 
+	fn cooldown(&self) -> Option<Duration> {
+		None
+	}
+
+	// the cached resources `run` reads, a command is refused if the cache isn't storing them.
+	fn cache_needs(&self) -> ResourceType {
+		ResourceType::empty()
+	}
+
+	// checked against the invoking member's permissions in the channel, overwrites included, before
+	// `run` is called.
+	fn required_permissions(&self) -> Permissions {
+		Permissions::empty()
+	}
+
 	#[allow(unused_variables)]
 	fn autocomplete<'a>(
 		&'a self,
@@ -24,8 +45,170 @@ pub trait SlashCommand: Send + Sync {
 	}
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandScope {
+	Global,
+	// only registered in the configured guild, skipped if there isn't one.
+	Guild,
+}
+
 pub trait DefineCommand: SlashCommand + Sized {
+	const SCOPE: CommandScope = CommandScope::Global;
+
 	fn define() -> CommandBuilder;
 
 	fn parse(data: CommandData) -> Result<Self>;
 }
+
+// a command made up of subcommands, like `/tag add`. grouped ones are named `group subcommand`.
+pub trait Subcommand: Sized {
+	fn parse_subcommand(name: &str, options: &[CommandDataOption]) -> Result<Self>;
+
+	fn parse_options(mut options: Vec<CommandDataOption>) -> Result<Self> {
+		if options.len() != 1 {
+			return Err(error!(
+				"expected exactly one subcommand, received {} options (this shouldn't happen)",
+				options.len()
+			));
+		}
+
+		let subcommand_value = options
+			.pop()
+			.ok_or_else(|| error!("failed to get subcommand value (this shouldn't happen)"))?;
+
+		match subcommand_value.value {
+			CommandOptionValue::SubCommand(v) => Self::parse_subcommand(&subcommand_value.name, &v),
+			CommandOptionValue::SubCommandGroup(mut v) => match (v.pop(), v.is_empty()) {
+				(
+					Some(CommandDataOption {
+						name,
+						value: CommandOptionValue::SubCommand(options),
+						..
+					}),
+					true,
+				) => {
+					Self::parse_subcommand(&format!("{} {}", subcommand_value.name, name), &options)
+				}
+				_ => Err(error!(
+					"expected exactly one subcommand in group `{}`",
+					subcommand_value.name
+				)),
+			},
+			_ => Err(error!("invalid subcommand value option")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::application::interaction::application_command::{
+		CommandDataOption, CommandOptionValue,
+	};
+
+	use super::Subcommand;
+	use crate::{helpers::parsing::CommandOptions, prelude::*};
+
+	#[derive(Debug, PartialEq, Eq)]
+	enum Settings {
+		Get { key: String },
+		Set { key: String, value: String },
+		RoleAdd { role: String },
+	}
+
+	impl Subcommand for Settings {
+		fn parse_subcommand(name: &str, options: &[CommandDataOption]) -> Result<Self> {
+			let find =
+				|option_name: &str| options.string_option(option_name).map(ToOwned::to_owned);
+
+			match name {
+				"get" => Ok(Self::Get {
+					key: find("key").unwrap_or_default(),
+				}),
+				"set" => Ok(Self::Set {
+					key: find("key").unwrap_or_default(),
+					value: find("value").unwrap_or_default(),
+				}),
+				"role add" => Ok(Self::RoleAdd {
+					role: find("role").unwrap_or_default(),
+				}),
+				_ => Err(error!("invalid subcommand variant")),
+			}
+		}
+	}
+
+	fn string_option(name: &str, value: &str) -> CommandDataOption {
+		CommandDataOption {
+			focused: false,
+			name: name.to_owned(),
+			value: CommandOptionValue::String(value.to_owned()),
+		}
+	}
+
+	fn subcommand(name: &str, options: Vec<CommandDataOption>) -> CommandDataOption {
+		CommandDataOption {
+			focused: false,
+			name: name.to_owned(),
+			value: CommandOptionValue::SubCommand(options),
+		}
+	}
+
+	#[test]
+	fn test_parse_subcommands() -> Result<()> {
+		let get = Settings::parse_options(vec![subcommand(
+			"get",
+			vec![string_option("key", "prefix")],
+		)])?;
+
+		assert_eq!(
+			get,
+			Settings::Get {
+				key: "prefix".to_owned()
+			}
+		);
+
+		let set = Settings::parse_options(vec![subcommand(
+			"set",
+			vec![string_option("key", "prefix"), string_option("value", "!")],
+		)])?;
+
+		assert_eq!(
+			set,
+			Settings::Set {
+				key: "prefix".to_owned(),
+				value: "!".to_owned()
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_rejects_flat_options() {
+		assert!(Settings::parse_options(vec![string_option("key", "prefix")]).is_err());
+		assert!(Settings::parse_options(Vec::new()).is_err());
+	}
+
+	#[test]
+	fn test_parse_subcommand_group() -> Result<()> {
+		let group = |options| CommandDataOption {
+			focused: false,
+			name: "role".to_owned(),
+			value: CommandOptionValue::SubCommandGroup(options),
+		};
+
+		let add = Settings::parse_options(vec![group(vec![subcommand(
+			"add",
+			vec![string_option("role", "mods")],
+		)])])?;
+
+		assert_eq!(
+			add,
+			Settings::RoleAdd {
+				role: "mods".to_owned()
+			}
+		);
+		assert!(Settings::parse_options(vec![group(Vec::new())]).is_err());
+
+		Ok(())
+	}
+}
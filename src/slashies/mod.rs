@@ -1,8 +1,16 @@
 pub mod commands;
+mod components;
+mod cooldown;
 mod r#impl;
+mod options;
+mod paginator;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+mod validate;
 
 use std::{fmt::Write, ops::Deref};
 
+use twilight_embed_builder::EmbedBuilder;
 use twilight_model::{
 	application::{
 		callback::{Autocomplete, CallbackData},
@@ -17,8 +25,22 @@ use twilight_model::{
 	id::{marker::UserMarker, Id},
 };
 
-pub use self::r#impl::{DefineCommand, SlashCommand};
-use crate::prelude::*;
+pub use self::{
+	components::{
+		ComponentHandler, ComponentRegistry, CustomId, CustomIdError, MAX_CUSTOM_ID_LENGTH,
+	},
+	cooldown::Cooldowns,
+	options::{check_range, CommandOptionExt, OptionError, MAX_CHOICES},
+	paginator::Paginator,
+	r#impl::{CommandScope, DefineCommand, SlashCommand, Subcommand},
+	validate::{
+		validate_callback, validate_command, CommandDefinitionError, ResponseError,
+		MAX_CONTENT_LENGTH, MAX_EMBEDS, MAX_EMBED_DESCRIPTION_LENGTH,
+	},
+};
+use crate::{helpers::ERROR_COLOR, prelude::*};
+
+pub const DEFAULT_LOCALE: &str = "en-US";
 
 #[derive(Debug, Clone)]
 #[must_use = "SlashData has no side effects"]
@@ -26,6 +48,9 @@ pub struct SlashData {
 	pub callback: CallbackData,
 	pub command: ApplicationCommand,
 	pub autocomplete: Autocomplete,
+	// loaded from the guild's and the user's settings before the command is run.
+	pub guild_locale: Option<String>,
+	pub user_locale: Option<String>,
 }
 
 impl SlashData {
@@ -43,9 +68,21 @@ impl SlashData {
 			callback: Self::BASE,
 			command,
 			autocomplete: Autocomplete { choices: vec![] },
+			guild_locale: None,
+			user_locale: None,
 		}
 	}
 
+	// a locale the user picked with `/preferences` wins over the guild's, which wins over the
+	// locale of the user's client.
+	#[must_use]
+	pub fn locale(&self) -> &str {
+		resolve_locale(
+			self.user_locale.as_deref().or(self.guild_locale.as_deref()),
+			&self.command.locale,
+		)
+	}
+
 	#[must_use]
 	#[allow(clippy::option_if_let_else)]
 	pub fn user_id(&self) -> Id<UserMarker> {
@@ -85,6 +122,20 @@ impl SlashData {
 			.into_diagnostic()
 	}
 
+	// folds the member's roles with the channel's overwrites. the cache's calculator gives guild
+	// owners and administrators every permission before looking at overwrites.
+	pub fn channel_permissions(&self, helper: &impl QuickAccess) -> Result<Permissions> {
+		if self.is_dm() {
+			return Err(error!("can't get user permissions in a DM"));
+		}
+
+		helper
+			.cache()
+			.permissions()
+			.in_channel(self.user_id(), self.channel_id)
+			.into_diagnostic()
+	}
+
 	pub fn allowed_mentions<F: FnOnce(AllowedMentionsBuilder) -> AllowedMentionsBuilder>(
 		&mut self,
 		builder: F,
@@ -102,7 +153,10 @@ impl SlashData {
 		self
 	}
 
-	pub fn autocomplete(&mut self, choices: Vec<CommandOptionChoice>) -> &mut Self {
+	// discord rejects more than 25 choices, so anything past that is dropped.
+	pub fn autocomplete(&mut self, mut choices: Vec<CommandOptionChoice>) -> &mut Self {
+		choices.truncate(MAX_CHOICES);
+
 		self.autocomplete = Autocomplete { choices };
 
 		self
@@ -137,6 +191,23 @@ impl SlashData {
 		self.flags(MessageFlags::EPHEMERAL)
 	}
 
+	// an ephemeral embed with a red sidebar, falling back to `error_text` if the message doesn't
+	// fit in an embed.
+	pub fn error(&mut self, message: String) -> &mut Self {
+		match error_embed(&message) {
+			Some(embed) => self.embed(embed).ephemeral(),
+			None => self.error_text(message),
+		}
+	}
+
+	pub fn error_text(&mut self, message: String) -> &mut Self {
+		self.message(message).ephemeral()
+	}
+
+	pub fn validate(&self) -> Result<(), ResponseError> {
+		validate_callback(&self.callback)
+	}
+
 	pub fn take(&mut self) -> Self {
 		Self {
 			callback: CallbackData {
@@ -149,6 +220,8 @@ impl SlashData {
 			},
 			command: self.command.clone(),
 			autocomplete: self.autocomplete.clone(),
+			guild_locale: self.guild_locale.clone(),
+			user_locale: self.user_locale.clone(),
 		}
 	}
 }
@@ -167,3 +240,49 @@ impl Write for SlashData {
 		Ok(())
 	}
 }
+
+fn error_embed(message: &str) -> Option<Embed> {
+	EmbedBuilder::new()
+		.color(ERROR_COLOR.to_decimal())
+		.title("Error")
+		.description(message)
+		.build()
+		.ok()
+}
+
+fn resolve_locale<'a>(configured: Option<&'a str>, interaction_locale: &'a str) -> &'a str {
+	match configured {
+		Some(locale) => locale,
+		None if !interaction_locale.is_empty() => interaction_locale,
+		None => DEFAULT_LOCALE,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{error_embed, resolve_locale, DEFAULT_LOCALE};
+	use crate::helpers::ERROR_COLOR;
+
+	#[test]
+	fn test_locale_precedence() {
+		assert_eq!(resolve_locale(Some("de"), "fr"), "de");
+		assert_eq!(resolve_locale(None, "fr"), "fr");
+		assert_eq!(resolve_locale(None, ""), DEFAULT_LOCALE);
+	}
+
+	#[test]
+	fn test_error_embed() {
+		let embed = error_embed("the tag already exists");
+
+		assert_eq!(
+			embed.as_ref().and_then(|embed| embed.color),
+			Some(ERROR_COLOR.to_decimal())
+		);
+		assert_eq!(
+			embed.and_then(|embed| embed.description).as_deref(),
+			Some("the tag already exists")
+		);
+
+		assert!(error_embed(&"a".repeat(5000)).is_none());
+	}
+}
@@ -0,0 +1,268 @@
+use std::ops::RangeInclusive;
+
+use twilight_model::application::command::{
+	CommandOption, CommandOptionChoice, CommandOptionValue as OptionBound, Number,
+};
+
+use crate::prelude::*;
+
+pub const MAX_CHOICES: usize = 25;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum OptionError {
+	#[error("an option can have at most {max} choices, but {0} were given", max = MAX_CHOICES)]
+	TooManyChoices(usize),
+	#[error("choices can only be added to string, integer, and number options")]
+	ChoicesUnsupported,
+	#[error("bounds can only be added to integer and number options")]
+	BoundsUnsupported,
+	#[error("the minimum value is greater than the maximum value")]
+	InvalidBounds,
+}
+
+// applied to an already built option, as in `CommandOption::from(StringBuilder::new(..))`.
+pub trait CommandOptionExt: Sized {
+	fn try_choices(self, choices: Vec<CommandOptionChoice>) -> Result<Self, OptionError>;
+
+	fn try_bounds(
+		self,
+		min_value: Option<OptionBound>,
+		max_value: Option<OptionBound>,
+	) -> Result<Self, OptionError>;
+
+	fn try_integer_bounds(
+		self,
+		min_value: Option<i64>,
+		max_value: Option<i64>,
+	) -> Result<Self, OptionError> {
+		if let (Some(min), Some(max)) = (min_value, max_value) {
+			if min > max {
+				return Err(OptionError::InvalidBounds);
+			}
+		}
+
+		self.try_bounds(
+			min_value.map(OptionBound::Integer),
+			max_value.map(OptionBound::Integer),
+		)
+	}
+
+	fn try_number_bounds(
+		self,
+		min_value: Option<f64>,
+		max_value: Option<f64>,
+	) -> Result<Self, OptionError> {
+		if let (Some(min), Some(max)) = (min_value, max_value) {
+			if min > max {
+				return Err(OptionError::InvalidBounds);
+			}
+		}
+
+		self.try_bounds(
+			min_value.map(|min| OptionBound::Number(Number(min))),
+			max_value.map(|max| OptionBound::Number(Number(max))),
+		)
+	}
+
+	fn try_string_choices<N, V, I>(self, choices: I) -> Result<Self, OptionError>
+	where
+		N: Into<String>,
+		V: Into<String>,
+		I: IntoIterator<Item = (N, V)>,
+	{
+		self.try_choices(
+			choices
+				.into_iter()
+				.map(|(name, value)| CommandOptionChoice::String {
+					name: name.into(),
+					value: value.into(),
+				})
+				.collect(),
+		)
+	}
+
+	fn try_integer_choices<N, I>(self, choices: I) -> Result<Self, OptionError>
+	where
+		N: Into<String>,
+		I: IntoIterator<Item = (N, i64)>,
+	{
+		self.try_choices(
+			choices
+				.into_iter()
+				.map(|(name, value)| CommandOptionChoice::Int {
+					name: name.into(),
+					value,
+				})
+				.collect(),
+		)
+	}
+}
+
+impl CommandOptionExt for CommandOption {
+	fn try_choices(mut self, choices: Vec<CommandOptionChoice>) -> Result<Self, OptionError> {
+		if choices.len() > MAX_CHOICES {
+			return Err(OptionError::TooManyChoices(choices.len()));
+		}
+
+		match &mut self {
+			Self::String(data) => data.choices = choices,
+			Self::Integer(data) | Self::Number(data) => data.choices = choices,
+			_ => return Err(OptionError::ChoicesUnsupported),
+		}
+
+		Ok(self)
+	}
+
+	fn try_bounds(
+		mut self,
+		min_value: Option<OptionBound>,
+		max_value: Option<OptionBound>,
+	) -> Result<Self, OptionError> {
+		match &mut self {
+			Self::Integer(data) | Self::Number(data) => {
+				data.min_value = min_value;
+				data.max_value = max_value;
+			}
+			_ => return Err(OptionError::BoundsUnsupported),
+		}
+
+		Ok(self)
+	}
+}
+
+// discord enforces option bounds client side, this re-checks them in case a client doesn't.
+pub fn check_range<T: PartialOrd + Display>(
+	name: &str,
+	value: T,
+	range: RangeInclusive<T>,
+) -> Result<T> {
+	if range.contains(&value) {
+		Ok(value)
+	} else {
+		Err(error!(
+			"`{}` must be between {} and {}, but was {}",
+			name,
+			range.start(),
+			range.end(),
+			value
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::application::command::{
+		CommandOption, CommandOptionChoice, CommandOptionValue as OptionBound, CommandType,
+	};
+	use twilight_util::builder::command::{
+		BooleanBuilder, CommandBuilder, IntegerBuilder, StringBuilder,
+	};
+
+	use super::{check_range, CommandOptionExt, OptionError};
+
+	#[test]
+	fn test_string_choices() -> Result<(), OptionError> {
+		let command = CommandBuilder::new(
+			"setcolor".to_owned(),
+			"Set a color".to_owned(),
+			CommandType::ChatInput,
+		)
+		.option(
+			CommandOption::from(StringBuilder::new(
+				"color".to_owned(),
+				"The color to use".to_owned(),
+			))
+			.try_string_choices([("Red", "red"), ("Green", "green"), ("Blue", "blue")])?,
+		)
+		.build();
+
+		if let CommandOption::String(data) = &command.options[0] {
+			assert_eq!(
+				data.choices,
+				[
+					CommandOptionChoice::String {
+						name: "Red".to_owned(),
+						value: "red".to_owned()
+					},
+					CommandOptionChoice::String {
+						name: "Green".to_owned(),
+						value: "green".to_owned()
+					},
+					CommandOptionChoice::String {
+						name: "Blue".to_owned(),
+						value: "blue".to_owned()
+					},
+				]
+			);
+		} else {
+			panic!("expected a string option");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_invalid_choices() {
+		let option = CommandOption::from(StringBuilder::new(
+			"number".to_owned(),
+			"A number".to_owned(),
+		));
+		let too_many = (0..26).map(|i| (i.to_string(), i.to_string()));
+
+		assert_eq!(
+			option.try_string_choices(too_many),
+			Err(OptionError::TooManyChoices(26))
+		);
+
+		let option =
+			CommandOption::from(BooleanBuilder::new("flag".to_owned(), "A flag".to_owned()));
+
+		assert_eq!(
+			option.try_string_choices([("yes", "yes")]),
+			Err(OptionError::ChoicesUnsupported)
+		);
+	}
+
+	#[test]
+	fn test_integer_bounds() -> Result<(), OptionError> {
+		let command = CommandBuilder::new(
+			"volume".to_owned(),
+			"Set the volume".to_owned(),
+			CommandType::ChatInput,
+		)
+		.option(
+			CommandOption::from(IntegerBuilder::new(
+				"level".to_owned(),
+				"The volume level".to_owned(),
+			))
+			.try_integer_bounds(Some(0), Some(100))?,
+		)
+		.build();
+
+		if let CommandOption::Integer(data) = &command.options[0] {
+			assert_eq!(data.min_value, Some(OptionBound::Integer(0)));
+			assert_eq!(data.max_value, Some(OptionBound::Integer(100)));
+		} else {
+			panic!("expected an integer option");
+		}
+
+		let option = CommandOption::from(IntegerBuilder::new(
+			"level".to_owned(),
+			"The volume level".to_owned(),
+		));
+
+		assert_eq!(
+			option.try_integer_bounds(Some(100), Some(0)),
+			Err(OptionError::InvalidBounds)
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_check_range() {
+		assert_eq!(check_range("level", 50, 0..=100).ok(), Some(50));
+		assert!(check_range("level", 101, 0..=100).is_err());
+		assert!(check_range("level", -1, 0..=100).is_err());
+	}
+}
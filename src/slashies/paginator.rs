@@ -0,0 +1,471 @@
+use std::{
+	collections::BTreeSet,
+	pin::Pin,
+	sync::{Mutex, MutexGuard, PoisonError},
+	time::Duration,
+};
+
+use futures_util::Future;
+use tokio::time::timeout;
+use twilight_model::{
+	application::{
+		callback::{CallbackData, InteractionResponse},
+		component::{button::ButtonStyle, ActionRow, Button, Component, SelectMenu},
+		interaction::MessageComponentInteraction,
+	},
+	channel::embed::Embed,
+	id::{
+		marker::{MessageMarker, UserMarker},
+		Id,
+	},
+};
+use twilight_standby::Standby;
+
+use super::SlashData;
+use crate::{
+	helpers::{InteractionsHelper, STARLIGHT_COLORS},
+	prelude::*,
+};
+
+// messages with a paginator still waiting on clicks, the rest were cut short by a restart.
+static RUNNING: Mutex<BTreeSet<Id<MessageMarker>>> = Mutex::new(BTreeSet::new());
+
+struct RunningGuard(Id<MessageMarker>);
+
+impl RunningGuard {
+	fn new(message_id: Id<MessageMarker>) -> Self {
+		running().insert(message_id);
+
+		Self(message_id)
+	}
+}
+
+impl Drop for RunningGuard {
+	fn drop(&mut self) {
+		running().remove(&self.0);
+	}
+}
+
+fn running() -> MutexGuard<'static, BTreeSet<Id<MessageMarker>>> {
+	RUNNING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageAction {
+	Previous,
+	Next,
+	Close,
+}
+
+impl PageAction {
+	const ALL: [Self; 3] = [Self::Previous, Self::Next, Self::Close];
+
+	const fn custom_id(self) -> &'static str {
+		match self {
+			Self::Previous => "paginator:previous",
+			Self::Next => "paginator:next",
+			Self::Close => "paginator:close",
+		}
+	}
+
+	const fn label(self) -> &'static str {
+		match self {
+			Self::Previous => "Previous",
+			Self::Next => "Next",
+			Self::Close => "Close",
+		}
+	}
+
+	fn from_custom_id(custom_id: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|action| action.custom_id() == custom_id)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[must_use = "a Paginator does nothing if not run"]
+pub struct Paginator {
+	pages: Vec<Embed>,
+	current: usize,
+	timeout: Duration,
+}
+
+impl Paginator {
+	pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+	// an empty list shows a single "nothing to show" page instead.
+	pub fn new(mut pages: Vec<Embed>) -> Self {
+		if pages.is_empty() {
+			pages.push(Self::empty_page());
+		}
+
+		Self {
+			pages,
+			current: 0,
+			timeout: Self::DEFAULT_TIMEOUT,
+		}
+	}
+
+	pub const fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+
+		self
+	}
+
+	// sends the first page, then swaps pages as the invoking user clicks until they close it or
+	// the timeout elapses, at which point the buttons are disabled.
+	pub async fn run(mut self, helper: InteractionsHelper, mut responder: SlashData) -> Result<()> {
+		if self.pages.len() == 1 {
+			responder.embeds(self.pages);
+			helper.respond(&mut responder).await.into_diagnostic()?;
+			return Ok(());
+		}
+
+		responder.callback = self.callback(&responder.callback, false);
+		helper.respond(&mut responder).await.into_diagnostic()?;
+
+		let message_id = helper.raw_get(&responder).await?.id;
+		let user_id = responder.user_id();
+		let _running = RunningGuard::new(message_id);
+
+		while let Some((event, open)) = self.next_click(helper.standby(), message_id, user_id).await
+		{
+			// clicks that aren't a page action still have to be acknowledged, or they show as failed.
+			let response = match open {
+				Some(open) => {
+					InteractionResponse::UpdateMessage(self.callback(&responder.callback, !open))
+				}
+				None => InteractionResponse::DeferredUpdateMessage,
+			};
+
+			helper
+				.interaction_client()
+				.create_response(event.id, &event.token, &response)
+				.exec()
+				.await
+				.into_diagnostic()?;
+
+			if open == Some(false) {
+				return Ok(());
+			}
+		}
+
+		responder.callback = self.callback(&responder.callback, true);
+		helper.update(&mut responder).await
+	}
+
+	fn empty_page() -> Embed {
+		Embed {
+			author: None,
+			color: Some(STARLIGHT_COLORS[0].to_decimal()),
+			description: Some("Nothing to show".to_owned()),
+			fields: Vec::new(),
+			footer: None,
+			image: None,
+			kind: "rich".to_owned(),
+			provider: None,
+			thumbnail: None,
+			timestamp: None,
+			title: None,
+			url: None,
+			video: None,
+		}
+	}
+
+	// registered for `paginator:`, so buttons left behind by a restart are disabled when clicked
+	// instead of failing. clicks on a running paginator are left to it.
+	pub fn expired(
+		helper: InteractionsHelper,
+		component: MessageComponentInteraction,
+	) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+		Box::pin(async move {
+			let is_running = running().contains(&component.message.id);
+			if is_running {
+				return Ok(());
+			}
+
+			let callback = CallbackData {
+				components: Some(disable_all(component.message.components)),
+				..SlashData::BASE
+			};
+
+			helper
+				.interaction_client()
+				.create_response(
+					component.id,
+					&component.token,
+					&InteractionResponse::UpdateMessage(callback),
+				)
+				.exec()
+				.await
+				.into_diagnostic()?;
+
+			Ok(())
+		})
+	}
+
+	// waits for the next click by `user_id` and applies it, returning the click and whether the
+	// paginator is still open (`None` if the click wasn't a page action). `None` once timed out.
+	async fn next_click(
+		&mut self,
+		standby: &Standby,
+		message_id: Id<MessageMarker>,
+		user_id: Id<UserMarker>,
+	) -> Option<(MessageComponentInteraction, Option<bool>)> {
+		let component = standby
+			.wait_for_component(message_id, move |event: &MessageComponentInteraction| {
+				event.author_id() == Some(user_id)
+			});
+
+		let event = match timeout(self.timeout, component).await {
+			Ok(Ok(event)) => event,
+			_ => return None,
+		};
+
+		let open =
+			PageAction::from_custom_id(&event.data.custom_id).map(|action| self.apply(action));
+
+		Some((event, open))
+	}
+
+	// returns whether the paginator is still open.
+	fn apply(&mut self, action: PageAction) -> bool {
+		match action {
+			PageAction::Previous => self.current = self.current.saturating_sub(1),
+			PageAction::Next => self.current = (self.current + 1).min(self.pages.len() - 1),
+			PageAction::Close => return false,
+		}
+
+		true
+	}
+
+	fn callback(&self, base: &CallbackData, disabled: bool) -> CallbackData {
+		CallbackData {
+			embeds: Some(vec![self.pages[self.current].clone()]),
+			components: Some(self.components(disabled)),
+			..base.clone()
+		}
+	}
+
+	fn components(&self, disabled: bool) -> Vec<Component> {
+		let last = self.pages.len() - 1;
+
+		let buttons = PageAction::ALL
+			.into_iter()
+			.map(|action| {
+				let at_edge = match action {
+					PageAction::Previous => self.current == 0,
+					PageAction::Next => self.current == last,
+					PageAction::Close => false,
+				};
+
+				Component::Button(Button {
+					custom_id: Some(action.custom_id().to_owned()),
+					disabled: disabled || at_edge,
+					emoji: None,
+					label: Some(action.label().to_owned()),
+					style: if action == PageAction::Close {
+						ButtonStyle::Danger
+					} else {
+						ButtonStyle::Primary
+					},
+					url: None,
+				})
+			})
+			.collect();
+
+		vec![Component::ActionRow(ActionRow {
+			components: buttons,
+		})]
+	}
+}
+
+fn disable_all(components: Vec<Component>) -> Vec<Component> {
+	components
+		.into_iter()
+		.map(|component| match component {
+			Component::ActionRow(row) => Component::ActionRow(ActionRow {
+				components: disable_all(row.components),
+			}),
+			Component::Button(button) => Component::Button(Button {
+				disabled: true,
+				..button
+			}),
+			Component::SelectMenu(menu) => Component::SelectMenu(SelectMenu {
+				disabled: true,
+				..menu
+			}),
+			other => other,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+	use twilight_gateway::Event;
+	use twilight_model::{
+		application::{
+			component::{Button, Component},
+			interaction::Interaction,
+		},
+		channel::embed::Embed,
+		gateway::payload::incoming::InteractionCreate,
+		id::Id,
+	};
+	use twilight_standby::Standby;
+
+	use super::{disable_all, PageAction, Paginator};
+
+	fn page(title: &str) -> Embed {
+		Embed {
+			author: None,
+			color: None,
+			description: None,
+			fields: Vec::new(),
+			footer: None,
+			image: None,
+			kind: "rich".to_owned(),
+			provider: None,
+			thumbnail: None,
+			timestamp: None,
+			title: Some(title.to_owned()),
+			url: None,
+			video: None,
+		}
+	}
+
+	fn buttons(paginator: &Paginator, disabled: bool) -> Vec<Button> {
+		match paginator.components(disabled).remove(0) {
+			Component::ActionRow(row) => row
+				.components
+				.into_iter()
+				.filter_map(|component| match component {
+					Component::Button(button) => Some(button),
+					_ => None,
+				})
+				.collect(),
+			_ => panic!("expected an action row"),
+		}
+	}
+
+	#[test]
+	fn test_page_transitions() {
+		let mut paginator = Paginator::new(vec![page("one"), page("two"), page("three")]);
+
+		assert_eq!(
+			PageAction::from_custom_id("paginator:next"),
+			Some(PageAction::Next)
+		);
+		assert!(buttons(&paginator, false)[0].disabled);
+
+		assert!(paginator.apply(PageAction::Next));
+		assert!(paginator.apply(PageAction::Next));
+		assert_eq!(paginator.current, 2);
+		assert!(paginator.apply(PageAction::Next));
+		assert_eq!(paginator.current, 2);
+		assert!(buttons(&paginator, false)[1].disabled);
+
+		assert!(paginator.apply(PageAction::Previous));
+		assert_eq!(paginator.current, 1);
+		assert!(!paginator.apply(PageAction::Close));
+		assert!(buttons(&paginator, true)
+			.iter()
+			.all(|button| button.disabled));
+
+		let stale = Paginator {
+			pages: paginator.pages.clone(),
+			current: 1,
+			timeout: Paginator::DEFAULT_TIMEOUT,
+		};
+		let disabled = disable_all(stale.components(false));
+		assert!(match &disabled[0] {
+			Component::ActionRow(row) => row.components.iter().all(|component| matches!(
+				component,
+				Component::Button(Button { disabled: true, .. })
+			)),
+			_ => false,
+		});
+	}
+
+	#[test]
+	fn test_empty_pages() {
+		let paginator = Paginator::new(Vec::new());
+
+		assert_eq!(paginator.pages.len(), 1);
+		assert_eq!(
+			paginator.pages[0].description.as_deref(),
+			Some("Nothing to show")
+		);
+	}
+
+	fn click(user_id: u64, custom_id: &str) -> Event {
+		let interaction: Interaction = serde_json::from_value(json!({
+			"application_id": "1",
+			"channel_id": "1",
+			"data": {
+				"component_type": 2,
+				"custom_id": custom_id,
+			},
+			"id": "4",
+			"locale": "en-US",
+			"message": {
+				"attachments": [],
+				"author": {
+					"avatar": null,
+					"discriminator": "0001",
+					"id": "9",
+					"username": "starlight",
+				},
+				"channel_id": "1",
+				"content": "",
+				"edited_timestamp": null,
+				"embeds": [],
+				"id": "3",
+				"mention_everyone": false,
+				"mention_roles": [],
+				"mentions": [],
+				"pinned": false,
+				"timestamp": "2021-01-01T00:00:00.000000+00:00",
+				"tts": false,
+				"type": 0,
+			},
+			"token": "token",
+			"type": 3,
+			"user": {
+				"avatar": null,
+				"discriminator": "0001",
+				"id": user_id.to_string(),
+				"username": "star",
+			},
+		}))
+		.unwrap();
+
+		Event::InteractionCreate(Box::new(InteractionCreate(interaction)))
+	}
+
+	#[tokio::test]
+	async fn test_clicks_through_standby() {
+		let standby = Standby::new();
+		let mut paginator = Paginator::new(vec![page("one"), page("two"), page("three")]);
+
+		for (custom_id, open, current) in [
+			("paginator:next", Some(true), 1),
+			("paginator:next", Some(true), 2),
+			("roles:add", None, 2),
+		] {
+			let (step, _) = tokio::join!(
+				paginator.next_click(&standby, Id::new(3), Id::new(2)),
+				async {
+					// clicks by anyone but the invoking user are ignored.
+					standby.process(&click(5, "paginator:previous"));
+					standby.process(&click(2, custom_id));
+				}
+			);
+
+			assert_eq!(step.map(|(_, open)| open), Some(open));
+			assert_eq!(paginator.current, current);
+		}
+	}
+}
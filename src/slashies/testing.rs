@@ -0,0 +1,151 @@
+use serde_json::json;
+use twilight_model::{
+	application::{
+		command::Number,
+		interaction::application_command::{CommandData, CommandDataOption, CommandOptionValue},
+	},
+	id::{
+		marker::{ChannelMarker, RoleMarker, UserMarker},
+		Id,
+	},
+};
+
+/// builds the `CommandData` discord would send for a command, for testing `DefineCommand::parse`.
+/// only compiled with the `test-util` feature, so run this with `cargo test --features test-util`.
+///
+/// ```
+/// use starlight::slashies::{commands::Settings, testing::CommandDataBuilder, DefineCommand};
+///
+/// let data = CommandDataBuilder::new("settings")
+/// 	.subcommand("locale", |options| options.string("locale", "de"))
+/// 	.build();
+///
+/// assert!(Settings::parse(data).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a CommandDataBuilder does nothing if not built"]
+pub struct CommandDataBuilder {
+	name: String,
+	options: Vec<CommandDataOption>,
+}
+
+impl CommandDataBuilder {
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			options: Vec::new(),
+		}
+	}
+
+	pub fn option(mut self, name: impl Into<String>, value: CommandOptionValue) -> Self {
+		self.options.push(CommandDataOption {
+			focused: false,
+			name: name.into(),
+			value,
+		});
+
+		self
+	}
+
+	pub fn string(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.option(name, CommandOptionValue::String(value.into()))
+	}
+
+	pub fn integer(self, name: impl Into<String>, value: i64) -> Self {
+		self.option(name, CommandOptionValue::Integer(value))
+	}
+
+	pub fn number(self, name: impl Into<String>, value: f64) -> Self {
+		self.option(name, CommandOptionValue::Number(Number(value)))
+	}
+
+	pub fn boolean(self, name: impl Into<String>, value: bool) -> Self {
+		self.option(name, CommandOptionValue::Boolean(value))
+	}
+
+	pub fn user(self, name: impl Into<String>, value: Id<UserMarker>) -> Self {
+		self.option(name, CommandOptionValue::User(value))
+	}
+
+	pub fn channel(self, name: impl Into<String>, value: Id<ChannelMarker>) -> Self {
+		self.option(name, CommandOptionValue::Channel(value))
+	}
+
+	pub fn role(self, name: impl Into<String>, value: Id<RoleMarker>) -> Self {
+		self.option(name, CommandOptionValue::Role(value))
+	}
+
+	// the options added in `f` are nested under the subcommand, like discord sends them.
+	pub fn subcommand<F>(self, name: impl Into<String>, f: F) -> Self
+	where
+		F: FnOnce(Self) -> Self,
+	{
+		let name = name.into();
+		let options = f(Self::new(name.clone())).options;
+
+		self.option(name, CommandOptionValue::SubCommand(options))
+	}
+
+	// marks the last added option as the one being autocompleted.
+	pub fn focused(mut self) -> Self {
+		if let Some(option) = self.options.last_mut() {
+			option.focused = true;
+		}
+
+		self
+	}
+
+	// goes through serde so fields this doesn't set (the resolved data) get their defaults.
+	#[must_use]
+	pub fn build(self) -> CommandData {
+		let mut data: CommandData = serde_json::from_value(json!({
+			"id": "1",
+			"name": self.name,
+			"type": 1,
+		}))
+		.expect("minimal command data is valid");
+
+		data.options = self.options;
+
+		data
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::{
+		application::interaction::application_command::CommandOptionValue, id::Id,
+	};
+
+	use super::CommandDataBuilder;
+	use crate::helpers::parsing::CommandOptions;
+
+	#[test]
+	fn test_build_options() {
+		let data = CommandDataBuilder::new("tag")
+			.subcommand("add", |options| {
+				options.string("name", "star").string("content", "light")
+			})
+			.build();
+
+		assert_eq!(data.name, "tag");
+		assert_eq!(data.options.len(), 1);
+
+		match &data.options[0].value {
+			CommandOptionValue::SubCommand(options) => {
+				assert_eq!(options.string_option("name"), Some("star"));
+				assert_eq!(options.string_option("content"), Some("light"));
+			}
+			_ => panic!("expected a subcommand"),
+		}
+
+		let data = CommandDataBuilder::new("inspect")
+			.integer("count", 5)
+			.user("user", Id::new(1))
+			.focused()
+			.build();
+
+		assert_eq!(data.integer_option("count"), Some(5));
+		assert!(data.options[1].focused);
+	}
+}
@@ -0,0 +1,202 @@
+use twilight_model::application::{callback::CallbackData, command::Command};
+use twilight_validate::command::{self as validate_command, CommandValidationError};
+
+use crate::prelude::*;
+
+pub const MAX_CONTENT_LENGTH: usize = 2000;
+pub const MAX_EMBEDS: usize = 10;
+pub const MAX_EMBED_DESCRIPTION_LENGTH: usize = 4096;
+
+#[derive(Debug, Error)]
+pub enum ResponseError {
+	#[error(
+		"content is {0} characters long, over the limit of {max}",
+		max = MAX_CONTENT_LENGTH
+	)]
+	ContentTooLong(usize),
+	#[error("{0} embeds were given, over the limit of {max}", max = MAX_EMBEDS)]
+	TooManyEmbeds(usize),
+	#[error(
+		"embed {index} has a {length} character description, over the limit of {max}",
+		max = MAX_EMBED_DESCRIPTION_LENGTH
+	)]
+	EmbedDescriptionTooLong { index: usize, length: usize },
+	#[error(transparent)]
+	Http(#[from] HttpError),
+}
+
+#[derive(Debug, Error)]
+#[error("command `{command}` has an invalid definition")]
+pub struct CommandDefinitionError {
+	pub command: String,
+	#[source]
+	pub source: CommandValidationError,
+}
+
+// checks a definition's name, description and options, so a bad command is caught before the
+// whole batch is rejected by discord.
+pub fn validate_command(command: &Command) -> Result<(), CommandDefinitionError> {
+	validate_command::command(command)
+		.and_then(|_| validate_command::options(&command.options))
+		.map_err(|source| CommandDefinitionError {
+			command: command.name.clone(),
+			source,
+		})
+}
+
+// checks the limits discord enforces, so violations fail locally instead of as an API error.
+pub fn validate_callback(callback: &CallbackData) -> Result<(), ResponseError> {
+	if let Some(content) = &callback.content {
+		let length = content.chars().count();
+		if length > MAX_CONTENT_LENGTH {
+			return Err(ResponseError::ContentTooLong(length));
+		}
+	}
+
+	if let Some(embeds) = &callback.embeds {
+		if embeds.len() > MAX_EMBEDS {
+			return Err(ResponseError::TooManyEmbeds(embeds.len()));
+		}
+
+		for (index, embed) in embeds.iter().enumerate() {
+			let length = embed
+				.description
+				.as_deref()
+				.map_or(0, |description| description.chars().count());
+
+			if length > MAX_EMBED_DESCRIPTION_LENGTH {
+				return Err(ResponseError::EmbedDescriptionTooLong { index, length });
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_model::{
+		application::{callback::CallbackData, command::CommandType},
+		channel::embed::Embed,
+	};
+	use twilight_util::builder::command::{BooleanBuilder, CommandBuilder};
+	use twilight_validate::command::CommandValidationErrorType;
+
+	use super::{validate_callback, validate_command, ResponseError};
+	use crate::{helpers::InteractionsHelper, slashies::SlashData};
+
+	fn embed() -> Embed {
+		Embed {
+			author: None,
+			color: None,
+			description: Some("description".to_owned()),
+			fields: Vec::new(),
+			footer: None,
+			image: None,
+			kind: "rich".to_owned(),
+			provider: None,
+			thumbnail: None,
+			timestamp: None,
+			title: None,
+			url: None,
+			video: None,
+		}
+	}
+
+	#[test]
+	fn test_content_length() {
+		let valid = CallbackData {
+			content: Some("a".repeat(2000)),
+			..SlashData::BASE
+		};
+
+		assert!(validate_callback(&valid).is_ok());
+
+		let too_long = CallbackData {
+			content: Some("a".repeat(2001)),
+			..SlashData::BASE
+		};
+
+		assert!(matches!(
+			validate_callback(&too_long),
+			Err(ResponseError::ContentTooLong(2001))
+		));
+	}
+
+	#[test]
+	fn test_embed_limits() {
+		let too_many = CallbackData {
+			embeds: Some(vec![embed(); 11]),
+			..SlashData::BASE
+		};
+
+		assert!(matches!(
+			validate_callback(&too_many),
+			Err(ResponseError::TooManyEmbeds(11))
+		));
+
+		let mut long_embed = embed();
+		long_embed.description = Some("a".repeat(4097));
+
+		let too_long = CallbackData {
+			embeds: Some(vec![embed(), long_embed]),
+			..SlashData::BASE
+		};
+
+		assert!(matches!(
+			validate_callback(&too_long),
+			Err(ResponseError::EmbedDescriptionTooLong {
+				index: 1,
+				length: 4097
+			})
+		));
+	}
+
+	#[test]
+	fn test_command_name_too_long() {
+		let command = CommandBuilder::new(
+			"a".repeat(33),
+			"description".to_owned(),
+			CommandType::ChatInput,
+		)
+		.build();
+
+		let error = validate_command(&command).unwrap_err();
+
+		assert_eq!(error.command, "a".repeat(33));
+		assert!(matches!(
+			error.source.kind(),
+			CommandValidationErrorType::NameLengthInvalid
+		));
+	}
+
+	#[test]
+	fn test_too_many_options() {
+		let command = (0..26)
+			.fold(
+				CommandBuilder::new(
+					"options".to_owned(),
+					"description".to_owned(),
+					CommandType::ChatInput,
+				),
+				|builder, index| {
+					builder.option(BooleanBuilder::new(
+						format!("option-{}", index),
+						"description".to_owned(),
+					))
+				},
+			)
+			.build();
+
+		let error = validate_command(&command).unwrap_err();
+
+		assert_eq!(error.command, "options");
+		assert!(matches!(
+			error.source.kind(),
+			CommandValidationErrorType::OptionsCountInvalid
+		));
+		assert!(InteractionsHelper::get_slashies()
+			.iter()
+			.all(|(command, _)| validate_command(command).is_ok()));
+	}
+}
@@ -1,7 +1,9 @@
 use std::{
 	env::VarError,
+	fmt::Debug,
 	path::{Path, PathBuf},
 	sync::Arc,
+	time::Instant,
 };
 
 use starchart::Starchart;
@@ -27,6 +29,20 @@ pub enum ContextBuildError {
 	Database,
 }
 
+/// only the setters that need the token return a `Result`, everything else chains directly.
+///
+/// ```no_run
+/// # use starlight::state::{Config, ContextBuilder, IntentsPreset};
+/// # fn main() -> miette::Result<()> {
+/// let builder = ContextBuilder::new()
+/// 	.config(Config::default())
+/// 	.intents(IntentsPreset::Minimal)
+/// 	.database_path("./target/db")
+/// 	.cdn_builder(|cdn| cdn)
+/// 	.shard_builder(|shard| shard)?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Default)]
 #[must_use = "a context builder has no side effects"]
 pub struct ContextBuilder {
@@ -37,6 +53,16 @@ pub struct ContextBuilder {
 	cdn: Option<reqwest::ClientBuilder>,
 	config: Option<Config>,
 	database_path: Option<PathBuf>,
+	database: Option<DatabaseBuilder>,
+}
+
+// run in `build`, once the database path is known, so the setters can be called in any order.
+struct DatabaseBuilder(Box<dyn FnOnce(&Path) -> Result<TomlBackend> + Send>);
+
+impl Debug for DatabaseBuilder {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str("DatabaseBuilder")
+	}
 }
 
 impl ContextBuilder {
@@ -49,17 +75,19 @@ impl ContextBuilder {
 			config: None,
 			cdn: None,
 			database_path: None,
+			database: None,
 		}
 	}
 
-	pub const fn config(mut self, config: Config) -> Self {
+	pub fn config(mut self, config: Config) -> Self {
 		self.config = Some(config);
 
 		self
 	}
 
-	pub const fn intents(mut self, intents: Intents) -> Self {
-		self.intents = Some(intents);
+	// takes either `Intents` or an `IntentsPreset`.
+	pub fn intents<I: Into<Intents>>(mut self, intents: I) -> Self {
+		self.intents = Some(intents.into());
 
 		self
 	}
@@ -96,13 +124,24 @@ impl ContextBuilder {
 		self
 	}
 
-	pub fn cdn_builder<F>(mut self, cdn_builder: F) -> Result<Self, reqwest::Error>
+	// lets the caller construct the backend themselves, it's passed the path set with
+	// `database_path` so the backend can be placed relative to it.
+	pub fn database_builder<F>(mut self, database_builder: F) -> Self
+	where
+		F: FnOnce(&Path) -> Result<TomlBackend> + Send + 'static,
+	{
+		self.database = Some(DatabaseBuilder(Box::new(database_builder)));
+
+		self
+	}
+
+	pub fn cdn_builder<F>(mut self, cdn_builder: F) -> Self
 	where
 		F: FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder,
 	{
 		self.cdn = Some(cdn_builder(reqwest::ClientBuilder::new()));
 
-		Ok(self)
+		self
 	}
 
 	pub fn http_builder<F>(mut self, http_builder_fn: F) -> Result<Self, VarError>
@@ -123,6 +162,7 @@ impl ContextBuilder {
 
 	pub async fn build(self) -> Result<(Context, Events)> {
 		let config = self.config.unwrap_or_default();
+		config.validate().into_diagnostic()?;
 		let token = Config::token().into_diagnostic()?;
 		let http_builder = self
 			.http
@@ -133,20 +173,19 @@ impl ContextBuilder {
 			.into_diagnostic()
 			.context("need cluster to build state")?;
 		let cdn_builder = self.cdn.unwrap_or_default();
-		let db_path = self
-			.database_path
-			.ok_or(ContextBuildError::Database)
-			.into_diagnostic()
-			.context("need database path to build state")?;
 
 		let cache_builder = self.cache.unwrap_or_default();
 
 		let http = Arc::new(http_builder.token(token).build());
 		let cache = Arc::new(cache_builder.build());
+		let shard_builder = match config.presence() {
+			Some(presence) => shard_builder.presence(presence),
+			None => shard_builder,
+		};
 		let (shard, events) = shard_builder.http_client(Arc::clone(&http)).build();
 		let cdn = cdn_builder.build().into_diagnostic()?;
 		let standby = Arc::default();
-		let backend = TomlBackend::new(db_path).into_diagnostic()?;
+		let backend = Self::backend(self.database, self.database_path)?;
 
 		let database = Starchart::new(backend).await.into_diagnostic()?;
 
@@ -158,8 +197,49 @@ impl ContextBuilder {
 			cdn,
 			config,
 			database,
+			runtime: Instant::now(),
 		}));
 
 		Ok((Context(components), events))
 	}
+
+	fn backend(database: Option<DatabaseBuilder>, path: Option<PathBuf>) -> Result<TomlBackend> {
+		let path = path
+			.ok_or(ContextBuildError::Database)
+			.into_diagnostic()
+			.context("need database path to build state")?;
+
+		match database {
+			Some(DatabaseBuilder(builder)) => builder(&path),
+			None => TomlBackend::new(path).into_diagnostic(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use starchart::Starchart;
+
+	use super::ContextBuilder;
+	use crate::prelude::*;
+
+	#[tokio::test]
+	async fn test_custom_backend() -> Result<()> {
+		let path = std::env::temp_dir().join(format!("starlight-backend-{}", std::process::id()));
+
+		// the builder is set first, the path it's given is only known later.
+		let builder = ContextBuilder::new()
+			.database_builder(|path| TomlBackend::new(path.join("custom")).into_diagnostic())
+			.database_path(&path);
+
+		let backend = ContextBuilder::backend(builder.database, builder.database_path)?;
+		Starchart::new(backend).await.into_diagnostic()?;
+
+		assert!(path.join("custom").is_dir());
+		assert!(ContextBuilder::backend(None, None).is_err());
+
+		std::fs::remove_dir_all(path).into_diagnostic()?;
+
+		Ok(())
+	}
 }
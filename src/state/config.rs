@@ -1,28 +1,71 @@
-use std::env::{self, VarError};
+use std::{
+	env::{self, VarError},
+	fs,
+	path::Path,
+};
 
 use clap::{
 	crate_authors, crate_description, crate_name, crate_version, App, Arg, ArgMatches,
 	Error as ClapError, FromArgMatches, IntoApp, Parser,
 };
-use miette::{IntoDiagnostic, Result};
+use miette::{IntoDiagnostic, Result, WrapErr};
+use serde::Deserialize;
+use thiserror::Error;
 use tracing::instrument;
-use twilight_model::id::{
-	marker::{ApplicationMarker, GuildMarker},
-	Id,
+use twilight_gateway::Intents;
+use twilight_model::{
+	gateway::{
+		payload::outgoing::update_presence::UpdatePresencePayload,
+		presence::{ActivityType, MinimalActivity, Status},
+	},
+	id::{
+		marker::{ApplicationMarker, GuildMarker, UserMarker},
+		Id,
+	},
 };
 
+use super::IntentsPreset;
+
 const REMOVE_SLASH_COMMANDS: &str = "remove-slash-commands";
+const REMOVE_COMMAND: &str = "remove-command";
+const DRY_RUN: &str = "dry-run";
 const GUILD_ID: &str = "guild-id";
+const OWNER_ID: &str = "owner-id";
+const STATUS: &str = "status";
+const ACTIVITY: &str = "activity";
+const ACTIVITY_TYPE: &str = "activity-type";
+const CONFIG: &str = "config";
 
 // static mut TOKEN: Option<&str> = None;
 const TOKEN: Option<&'static str> = option_env!("DISCORD_TOKEN");
 
 static mut APPLICATION_ID: Option<Id<ApplicationMarker>> = None;
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+	#[error("no token was found, set `DISCORD_TOKEN` in the environment or in a `.env` file")]
+	MissingToken,
+	#[error("the token is malformed, it should be three non-empty parts separated by `.`")]
+	MalformedToken,
+	#[error("the first part of the token doesn't decode to an application id")]
+	InvalidApplicationId,
+	#[error("the activity name is empty, set `STARLIGHT_ACTIVITY` or remove it")]
+	EmptyActivity,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct Config {
+	// release builds only register `CommandScope::Guild` commands here, debug builds register every
+	// command here.
 	pub guild_id: Option<Id<GuildMarker>>,
+	pub owner_id: Option<Id<UserMarker>>,
 	pub remove_slash_commands: bool,
+	// only these commands are removed when set, otherwise every command is.
+	pub remove_commands: Vec<String>,
+	// logs the commands that would be removed and starts normally instead.
+	pub dry_run: bool,
+	pub status: Option<Status>,
+	pub activity: Option<MinimalActivity>,
 }
 
 impl Config {
@@ -35,17 +78,181 @@ impl Config {
 
 		let token = Self::token().into_diagnostic()?;
 
-		let first_part = token.split('.').next().unwrap_or_default();
+		let id = Self::validate_token(&token).into_diagnostic()?;
 
-		let decoded = base64::decode(first_part).into_diagnostic()?;
+		unsafe { APPLICATION_ID = Some(id) };
 
-		let value = unsafe { String::from_utf8_unchecked(decoded) }
-			.parse()
-			.into_diagnostic()?;
+		Ok(id)
+	}
+
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		let token = Self::token().map_err(|_| ConfigError::MissingToken)?;
+
+		Self::validate_token(&token)?;
+
+		if let Some(activity) = &self.activity {
+			if activity.name.trim().is_empty() {
+				return Err(ConfigError::EmptyActivity);
+			}
+		}
+
+		Ok(())
+	}
+
+	// a token is the base64 encoded application id, a timestamp, and an hmac, joined by `.`.
+	fn validate_token(token: &str) -> Result<Id<ApplicationMarker>, ConfigError> {
+		if token.trim().is_empty() {
+			return Err(ConfigError::MissingToken);
+		}
+
+		let parts = token.split('.').collect::<Vec<_>>();
+
+		if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+			return Err(ConfigError::MalformedToken);
+		}
+
+		base64::decode(parts[0])
+			.ok()
+			.and_then(|decoded| String::from_utf8(decoded).ok())
+			.and_then(|id| id.parse().ok())
+			.and_then(Id::new_checked)
+			.ok_or(ConfigError::InvalidApplicationId)
+	}
+
+	fn owner_id_from_matches(matches: &ArgMatches) -> Result<Option<Id<UserMarker>>, ClapError> {
+		match matches.value_of_t::<u64>(OWNER_ID) {
+			Ok(o) => Ok(Id::new_checked(o)),
+			Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	fn parse_status(status: &str) -> Option<Status> {
+		match status {
+			"online" => Some(Status::Online),
+			"idle" => Some(Status::Idle),
+			"dnd" => Some(Status::DoNotDisturb),
+			"invisible" => Some(Status::Invisible),
+			_ => None,
+		}
+	}
+
+	fn parse_activity_type(kind: &str) -> Option<ActivityType> {
+		match kind {
+			"playing" => Some(ActivityType::Playing),
+			"listening" => Some(ActivityType::Listening),
+			"watching" => Some(ActivityType::Watching),
+			"competing" => Some(ActivityType::Competing),
+			_ => None,
+		}
+	}
+
+	// clap only accepts the possible values, so the fallbacks are never hit.
+	fn presence_from_matches(matches: &ArgMatches) -> (Option<Status>, Option<MinimalActivity>) {
+		let status = matches
+			.value_of(STATUS)
+			.map(|status| Self::parse_status(status).unwrap_or(Status::Online));
+
+		let activity = matches.value_of(ACTIVITY).map(|name| MinimalActivity {
+			kind: matches
+				.value_of(ACTIVITY_TYPE)
+				.and_then(Self::parse_activity_type)
+				.unwrap_or(ActivityType::Playing),
+			name: name.to_owned(),
+			url: None,
+		});
 
-		unsafe { APPLICATION_ID = Id::new_checked(value) };
+		(status, activity)
+	}
+
+	fn remove_commands_from_matches(matches: &ArgMatches) -> Vec<String> {
+		matches
+			.values_of(REMOVE_COMMAND)
+			.map(|names| names.map(str::to_owned).collect())
+			.unwrap_or_default()
+	}
+
+	// the file is read first, so anything passed on the command line or set in the environment
+	// overrides it.
+	pub fn load() -> Result<Self> {
+		let matches = Self::into_app().get_matches();
+
+		let mut config = match matches.value_of(CONFIG) {
+			Some(path) => Self::from_file(path)?,
+			None => Self::default(),
+		};
+
+		config.update_from_arg_matches(&matches).into_diagnostic()?;
+
+		Ok(config)
+	}
+
+	pub fn from_env() -> Result<Self> {
+		Self::try_parse_from([crate_name!()]).into_diagnostic()
+	}
+
+	pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+		let path = path.as_ref();
+
+		let contents = fs::read_to_string(path)
+			.into_diagnostic()
+			.wrap_err_with(|| format!("failed to read config file {}", path.display()))?;
+
+		Self::from_toml(&contents)
+	}
+
+	fn from_toml(contents: &str) -> Result<Self> {
+		let file: ConfigFile = toml::from_str(contents).into_diagnostic()?;
 
-		Ok(unsafe { Id::new_unchecked(value) })
+		let status = file
+			.status
+			.map(|status| {
+				Self::parse_status(&status)
+					.ok_or_else(|| miette::miette!("invalid status `{}`", status))
+			})
+			.transpose()?;
+
+		let kind = file
+			.activity_type
+			.map(|kind| {
+				Self::parse_activity_type(&kind)
+					.ok_or_else(|| miette::miette!("invalid activity type `{}`", kind))
+			})
+			.transpose()?
+			.unwrap_or(ActivityType::Playing);
+
+		Ok(Self {
+			guild_id: file.guild_id,
+			owner_id: file.owner_id,
+			remove_slash_commands: false,
+			remove_commands: Vec::new(),
+			dry_run: false,
+			status,
+			activity: file.activity.map(|name| MinimalActivity {
+				kind,
+				name,
+				url: None,
+			}),
+		})
+	}
+
+	#[must_use]
+	pub const fn default_intents() -> Intents {
+		IntentsPreset::Default.intents()
+	}
+
+	// discord requires at least one activity, so a status without one isn't sent.
+	#[must_use]
+	pub fn presence(&self) -> Option<UpdatePresencePayload> {
+		let activity = self.activity.clone()?;
+
+		UpdatePresencePayload::new(
+			vec![activity.into()],
+			false,
+			None,
+			self.status.unwrap_or(Status::Online),
+		)
+		.ok()
 	}
 
 	#[instrument]
@@ -54,6 +261,16 @@ impl Config {
 	}
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct ConfigFile {
+	guild_id: Option<Id<GuildMarker>>,
+	owner_id: Option<Id<UserMarker>>,
+	status: Option<String>,
+	activity: Option<String>,
+	activity_type: Option<String>,
+}
+
 impl IntoApp for Config {
 	fn into_app<'help>() -> App<'help> {
 		App::new(crate_name!())
@@ -61,16 +278,61 @@ impl IntoApp for Config {
 			.version(crate_version!())
 			.author(crate_authors!())
 			.args(&[
+				Arg::new(CONFIG)
+					.help("TOML file to read the configuration from")
+					.env("STARLIGHT_CONFIG_FILE")
+					.long("config")
+					.short('c')
+					.takes_value(true),
 				Arg::new(GUILD_ID)
-					.help("Guild ID to use for testing")
+					.help(
+						"Guild ID for guild scoped commands, and for every command in debug builds",
+					)
 					.env("GUILD_ID")
 					.long("guild-id")
 					.short('g')
 					.takes_value(true),
+				Arg::new(OWNER_ID)
+					.help("User ID allowed to run owner-only commands")
+					.env("STARLIGHT_OWNER_ID")
+					.long("owner-id")
+					.short('o')
+					.takes_value(true),
+				Arg::new(STATUS)
+					.help("Status to show once connected")
+					.env("STARLIGHT_STATUS")
+					.long("status")
+					.possible_values(["online", "idle", "dnd", "invisible"])
+					.takes_value(true),
+				Arg::new(ACTIVITY)
+					.help("Activity name to show once connected")
+					.env("STARLIGHT_ACTIVITY")
+					.long("activity")
+					.takes_value(true),
+				Arg::new(ACTIVITY_TYPE)
+					.help("Kind of activity to show")
+					.env("STARLIGHT_ACTIVITY_TYPE")
+					.long("activity-type")
+					.possible_values(["playing", "listening", "watching", "competing"])
+					.requires(ACTIVITY)
+					.takes_value(true),
 				Arg::new(REMOVE_SLASH_COMMANDS)
 					.help("Removes the global slash commands and exits")
 					.env("DELETE_SLASH_COMMANDS")
 					.long("delete-slash-commands"),
+				Arg::new(REMOVE_COMMAND)
+					.help("Name of a slash command to remove, instead of all of them")
+					.env("STARLIGHT_REMOVE_COMMANDS")
+					.long("remove-command")
+					.takes_value(true)
+					.multiple_occurrences(true)
+					.use_delimiter(true)
+					.requires(REMOVE_SLASH_COMMANDS),
+				Arg::new(DRY_RUN)
+					.help("Logs the slash commands that would be removed without removing them")
+					.env("STARLIGHT_DRY_RUN")
+					.long("dry-run")
+					.requires(REMOVE_SLASH_COMMANDS),
 			])
 	}
 
@@ -81,39 +343,229 @@ impl IntoApp for Config {
 
 impl FromArgMatches for Config {
 	fn from_arg_matches(matches: &ArgMatches) -> Result<Self, ClapError> {
-		let guild_id = if cfg!(debug_assertions) {
-			match matches.value_of_t::<u64>(GUILD_ID) {
-				Ok(g) => Id::new_checked(g),
-				Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => None,
-				Err(e) => return Err(e),
-			}
-		} else {
-			None
+		let guild_id = match matches.value_of_t::<u64>(GUILD_ID) {
+			Ok(g) => Id::new_checked(g),
+			Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => None,
+			Err(e) => return Err(e),
 		};
 
+		let owner_id = Self::owner_id_from_matches(matches)?;
+
+		let (status, activity) = Self::presence_from_matches(matches);
+
 		Ok(Self {
 			guild_id,
+			owner_id,
 			remove_slash_commands: matches.is_present(REMOVE_SLASH_COMMANDS),
+			remove_commands: Self::remove_commands_from_matches(matches),
+			dry_run: matches.is_present(DRY_RUN),
+			status,
+			activity,
 		})
 	}
 
+	// only overrides the values that were passed, keeping the rest (such as those from a file).
 	fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), ClapError> {
-		let guild_id = if cfg!(debug_assertions) {
-			match matches.value_of_t::<u64>(GUILD_ID) {
-				Ok(g) => Id::new_checked(g),
-				Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => None,
-				Err(e) => return Err(e),
-			}
-		} else {
-			None
-		};
+		match matches.value_of_t::<u64>(GUILD_ID) {
+			Ok(g) => self.guild_id = Id::new_checked(g),
+			Err(e) if e.kind == clap::ErrorKind::ArgumentNotFound => {}
+			Err(e) => return Err(e),
+		}
+
+		if let Some(owner_id) = Self::owner_id_from_matches(matches)? {
+			self.owner_id = Some(owner_id);
+		}
 
-		self.guild_id = guild_id;
+		self.remove_slash_commands |= matches.is_present(REMOVE_SLASH_COMMANDS);
+		self.dry_run |= matches.is_present(DRY_RUN);
 
-		self.remove_slash_commands = matches.is_present(REMOVE_SLASH_COMMANDS);
+		let remove_commands = Self::remove_commands_from_matches(matches);
+
+		if !remove_commands.is_empty() {
+			self.remove_commands = remove_commands;
+		}
+
+		let (status, activity) = Self::presence_from_matches(matches);
+
+		if status.is_some() {
+			self.status = status;
+		}
+
+		if activity.is_some() {
+			self.activity = activity;
+		}
 
 		Ok(())
 	}
 }
 
 impl Parser for Config {}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		env::{self, VarError},
+		ffi::OsString,
+		sync::{Mutex, MutexGuard, PoisonError},
+	};
+
+	use clap::{crate_name, FromArgMatches, IntoApp, Parser};
+	use miette::{IntoDiagnostic, Result};
+	use serde_json::json;
+	use twilight_model::gateway::{
+		payload::outgoing::UpdatePresence,
+		presence::{ActivityType, MinimalActivity, Status},
+	};
+
+	use super::{Config, ConfigError, TOKEN};
+
+	// the environment is shared by every test thread, so tests touching it take this first.
+	static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+	fn lock_env() -> MutexGuard<'static, ()> {
+		ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	// restores the previous value of the variable when dropped.
+	struct EnvGuard {
+		key: &'static str,
+		previous: Option<OsString>,
+	}
+
+	impl EnvGuard {
+		fn set(key: &'static str, value: Option<&str>) -> Self {
+			let previous = env::var_os(key);
+
+			match value {
+				Some(value) => env::set_var(key, value),
+				None => env::remove_var(key),
+			}
+
+			Self { key, previous }
+		}
+	}
+
+	impl Drop for EnvGuard {
+		fn drop(&mut self) {
+			match self.previous.take() {
+				Some(value) => env::set_var(self.key, value),
+				None => env::remove_var(self.key),
+			}
+		}
+	}
+
+	#[test]
+	fn test_config_precedence() -> Result<()> {
+		let _env = lock_env();
+		let _owner = EnvGuard::set("STARLIGHT_OWNER_ID", Some("2"));
+		let _status = EnvGuard::set("STARLIGHT_STATUS", None);
+		let _activity = EnvGuard::set("STARLIGHT_ACTIVITY", None);
+
+		let mut config = Config::from_toml(
+			r#"
+			owner-id = "1"
+			status = "idle"
+			"#,
+		)?;
+
+		assert_eq!(config.owner_id.map(|id| id.get()), Some(1));
+
+		let matches = Config::into_app()
+			.try_get_matches_from([crate_name!()])
+			.into_diagnostic()?;
+		config.update_from_arg_matches(&matches).into_diagnostic()?;
+
+		assert_eq!(config.owner_id.map(|id| id.get()), Some(2));
+		assert_eq!(config.status, Some(Status::Idle));
+		assert!(config.activity.is_none());
+		assert!(!config.remove_slash_commands);
+
+		assert!(Config::from_toml(r#"status = "sleeping""#).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selective_removal() -> Result<()> {
+		let _env = lock_env();
+		let _remove = EnvGuard::set("DELETE_SLASH_COMMANDS", None);
+		let _names = EnvGuard::set("STARLIGHT_REMOVE_COMMANDS", None);
+		let _dry_run = EnvGuard::set("STARLIGHT_DRY_RUN", None);
+
+		let config = Config::try_parse_from([
+			crate_name!(),
+			"--delete-slash-commands",
+			"--remove-command",
+			"ping,tag",
+			"--remove-command",
+			"stats",
+			"--dry-run",
+		])
+		.into_diagnostic()?;
+
+		assert!(config.remove_slash_commands);
+		assert!(config.dry_run);
+		assert_eq!(config.remove_commands, ["ping", "tag", "stats"]);
+
+		assert!(Config::try_parse_from([crate_name!(), "--remove-command", "ping"]).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_validate_token() {
+		assert_eq!(Config::validate_token(""), Err(ConfigError::MissingToken));
+		assert_eq!(
+			Config::validate_token("not a token"),
+			Err(ConfigError::MalformedToken)
+		);
+		assert_eq!(
+			Config::validate_token("MTIz..abc"),
+			Err(ConfigError::MalformedToken)
+		);
+		assert_eq!(
+			Config::validate_token("bm90IGFuIGlk.abc.def"),
+			Err(ConfigError::InvalidApplicationId)
+		);
+		assert_eq!(
+			Config::validate_token("MTIzNDU2Nzg5MDEyMzQ1Njc4.YbNvDA.c2VjcmV0").map(|id| id.get()),
+			Ok(123_456_789_012_345_678)
+		);
+	}
+
+	#[test]
+	fn test_missing_token() {
+		let _env = lock_env();
+		let _token = EnvGuard::set("DISCORD_TOKEN", None);
+
+		if TOKEN.is_none() {
+			assert!(matches!(Config::token(), Err(VarError::NotPresent)));
+			assert_eq!(Config::default().validate(), Err(ConfigError::MissingToken));
+		}
+	}
+
+	#[test]
+	fn test_presence_payload() {
+		assert!(Config::default().presence().is_none());
+
+		let config = Config {
+			status: Some(Status::Idle),
+			activity: Some(MinimalActivity {
+				kind: ActivityType::Watching,
+				name: "3 servers".to_owned(),
+				url: None,
+			}),
+			..Config::default()
+		};
+
+		let presence = config.presence().expect("an activity was set");
+		let command = UpdatePresence::new(presence.activities, presence.afk, None, presence.status)
+			.expect("an activity was set");
+		let value = serde_json::to_value(&command).expect("failed to serialize presence");
+
+		assert_eq!(value["op"], json!(3));
+		assert_eq!(value["d"]["status"], json!("idle"));
+		assert_eq!(value["d"]["activities"][0]["name"], json!("3 servers"));
+		assert_eq!(value["d"]["activities"][0]["type"], json!(3));
+	}
+}
@@ -1,12 +1,12 @@
 use std::convert::Infallible;
 
-use starchart::{action::CreateEntryAction, Action, Result as ChartResult};
 use tracing::{event, Level};
 use twilight_gateway::Event;
 use twilight_model::{
 	application::interaction::Interaction,
 	gateway::payload::incoming::{InteractionCreate, Ready},
 	guild::Guild,
+	id::{marker::GuildMarker, Id},
 };
 
 use super::Context;
@@ -20,11 +20,9 @@ use crate::{
 pub(super) async fn handle(context: Context, event: Event) {
 	if let Err(e) = match event {
 		Event::Ready(e) => ready(context, *e).await.into_diagnostic(),
-		Event::GuildCreate(e) => guild_create(context, (*e).0).await.into_diagnostic(),
-		Event::InteractionCreate(e) => {
-			interaction_create(context, *e).await;
-			Ok(())
-		}
+		Event::GuildCreate(e) => guild_create(context, (*e).0).await,
+		Event::GuildDelete(e) => guild_delete(context, e.id, e.unavailable).await,
+		Event::InteractionCreate(e) => interaction_create(context, *e).await,
 		_ => Ok(()),
 	} {
 		event!(Level::ERROR, "error occurred: {:?}", e);
@@ -38,28 +36,45 @@ async fn ready(_: Context, ready: Ready) -> Result<(), Infallible> {
 	Ok(())
 }
 
-async fn guild_create(context: Context, guild: Guild) -> ChartResult<()> {
-	let id = guild.id;
-	let database = context.database();
-
-	let mut action: CreateEntryAction<GuildSettings> = Action::new();
+async fn guild_create(context: Context, guild: Guild) -> Result<()> {
+	Tables::Guilds
+		.create_entry(context.database(), &GuildSettings::new(guild.id))
+		.await
+}
 
-	let table = Tables::Guilds.to_string();
-	let entry = GuildSettings::new(id);
+// an unavailable guild is only in an outage, the bot hasn't left it.
+async fn guild_delete(
+	context: Context,
+	guild_id: Id<GuildMarker>,
+	unavailable: bool,
+) -> Result<()> {
+	if unavailable {
+		return Ok(());
+	}
 
-	action.set_entry(&entry).set_table(&table);
+	let removed = Tables::Guilds
+		.delete_entry::<GuildSettings>(context.database(), &guild_id)
+		.await?;
 
-	action.run_create_entry(database).await?;
+	event!(Level::INFO, %guild_id, removed, "removed guild settings");
 
 	Ok(())
 }
 
-async fn interaction_create(context: Context, interaction: InteractionCreate) {
+async fn interaction_create(context: Context, interaction: InteractionCreate) -> Result<()> {
 	match interaction.0 {
 		Interaction::ApplicationCommand(cmd) | Interaction::ApplicationCommandAutocomplete(cmd) => {
 			context.helpers().interactions().handle(*cmd).await;
 		}
-		Interaction::MessageComponent(_) => {}
+		Interaction::MessageComponent(component) => {
+			context
+				.helpers()
+				.interactions()
+				.handle_component(*component)
+				.await;
+		}
 		i => event!(Level::WARN, ?i, "unhandled interaction"),
 	}
+
+	Ok(())
 }
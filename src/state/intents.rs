@@ -0,0 +1,75 @@
+use twilight_gateway::Intents;
+
+use crate::prelude::*;
+
+// common sets of gateway intents. privileged ones have to be enabled in the developer portal, or
+// the gateway closes the connection on identify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentsPreset {
+	// guild events only, enough for slash commands and guild settings.
+	Minimal,
+	// guilds, members and guild messages.
+	Default,
+	Full,
+}
+
+impl IntentsPreset {
+	pub const PRIVILEGED: Intents = Intents::from_bits_truncate(
+		Intents::GUILD_MEMBERS.bits() | Intents::GUILD_PRESENCES.bits(),
+	);
+
+	#[must_use]
+	pub const fn intents(self) -> Intents {
+		match self {
+			Self::Minimal => Intents::GUILDS,
+			Self::Default => Intents::from_bits_truncate(
+				Intents::GUILDS.bits()
+					| Intents::GUILD_MEMBERS.bits()
+					| Intents::GUILD_MESSAGES.bits(),
+			),
+			Self::Full => Intents::all(),
+		}
+	}
+
+	#[must_use]
+	pub const fn is_privileged(self) -> bool {
+		self.intents().intersects(Self::PRIVILEGED)
+	}
+}
+
+impl From<IntentsPreset> for Intents {
+	fn from(preset: IntentsPreset) -> Self {
+		preset.intents()
+	}
+}
+
+impl Display for IntentsPreset {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str(match self {
+			Self::Minimal => "minimal",
+			Self::Default => "default",
+			Self::Full => "full",
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use twilight_gateway::Intents;
+
+	use super::IntentsPreset;
+
+	#[test]
+	fn test_preset_bits() {
+		assert_eq!(IntentsPreset::Minimal.intents(), Intents::GUILDS);
+		assert_eq!(
+			IntentsPreset::Default.intents(),
+			Intents::GUILDS | Intents::GUILD_MEMBERS | Intents::GUILD_MESSAGES
+		);
+		assert_eq!(Intents::from(IntentsPreset::Full), Intents::all());
+
+		assert!(!IntentsPreset::Minimal.is_privileged());
+		assert!(IntentsPreset::Default.is_privileged());
+		assert!(IntentsPreset::Full.is_privileged());
+	}
+}
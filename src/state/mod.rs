@@ -1,43 +1,78 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+	ops::Deref,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
-use futures_util::StreamExt;
+use futures_util::{Future, StreamExt};
 use starchart::Starchart;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+#[cfg(windows)]
+use tokio::signal::windows::{ctrl_break, ctrl_c};
 use tracing::{event, Level};
 use twilight_cache_inmemory::InMemoryCache as Cache;
-use twilight_gateway::{shard::Events, Event, Shard};
+use twilight_gateway::{
+	shard::{Events, Stage},
+	Event, Shard,
+};
 use twilight_http::{client::InteractionClient, Client as HttpClient};
+use twilight_model::gateway::payload::outgoing::UpdatePresence;
 use twilight_standby::Standby;
 
 use self::events::handle;
-pub use self::{builder::ContextBuilder, config::Config};
+pub use self::{
+	builder::ContextBuilder,
+	config::{Config, ConfigError},
+	intents::IntentsPreset,
+	pending::PendingTasks,
+};
 use crate::{helpers::Helpers, prelude::*, settings::Tables};
 
 mod builder;
 mod config;
 mod events;
+mod intents;
+mod pending;
+pub(crate) mod telemetry;
+
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy)]
 pub struct Context(pub &'static State);
 
 impl Context {
 	pub async fn connect(self) -> Result<()> {
-		let id = Config::application_id()?;
-		let interaction_client = self.http.interaction(id);
+		// caches the application id, so `interaction_client` can't fail afterwards.
+		Config::application_id()?;
 
 		if self.0.config.remove_slash_commands {
-			if let Some(guild_id) = self.0.config.guild_id {
-				event!(Level::INFO, %guild_id, "removing all slash commands in guild");
-				interaction_client
-					.set_guild_commands(guild_id, &[])
-					.exec()
-					.await
-			} else {
-				event!(Level::INFO, "removing all global slash commands");
-				interaction_client.set_global_commands(&[]).exec().await
+			let dry_run = self.0.config.dry_run;
+
+			// guild scoped commands (and every command in debug builds) live in the configured
+			// guild, so that's cleared along with the global commands whatever the build profile.
+			let targets = std::iter::once(None).chain(self.0.config.guild_id.map(Some));
+
+			for guild_id in targets {
+				let removed = self
+					.helpers()
+					.interactions()
+					.remove_commands(guild_id, &self.0.config.remove_commands, dry_run)
+					.await?;
+
+				event!(
+					Level::INFO,
+					?guild_id,
+					count = removed.len(),
+					dry_run,
+					"removed slash commands"
+				);
 			}
-			.into_diagnostic()?;
 
-			std::process::exit(0);
+			// a dry run only reports, so the bot starts as usual afterwards.
+			if !dry_run {
+				std::process::exit(0);
+			}
 		}
 
 		event!(Level::INFO, "setting slash commands");
@@ -63,20 +98,81 @@ impl Context {
 		event!(Level::ERROR, "event stream exhausted (shouldn't happen)");
 	}
 
+	// processes events until SIGINT or SIGTERM (Ctrl-C or Ctrl-Break on windows), then shuts down.
+	pub async fn run_until_shutdown(self, events: Events) -> Result<()> {
+		until_signal(self.process(events), shutdown_signal()?).await;
+
+		event!(Level::INFO, "shutting down");
+
+		self.shutdown().await;
+
+		Ok(())
+	}
+
 	pub const fn helpers(self) -> Helpers {
 		Helpers::new(self)
 	}
 
-	pub fn shutdown(self) {
+	// replaces the presence on the bot's single shard, the initial one comes from `Config::presence`.
+	pub async fn set_presence(self, presence: UpdatePresence) -> Result<()> {
+		self.0.shard.command(&presence).await.into_diagnostic()
+	}
+
+	// stops the shard, then waits for the database writes in progress so they land before the
+	// process exits.
+	pub async fn shutdown(self) {
 		self.0.shard.shutdown();
+
+		if !Tables::flush(SHUTDOWN_TIMEOUT).await {
+			event!(
+				Level::WARN,
+				"database writes still running after {:?}, some may be lost",
+				SHUTDOWN_TIMEOUT
+			);
+		}
 	}
 
 	pub fn handle_event(&self, event: &Event) {
+		telemetry::event_processed(event);
 		self.0.cache.update(event);
 		self.0.standby.process(event);
 	}
 }
 
+// runs `process` until `signal` resolves, returning whether it was the signal that ended it.
+async fn until_signal(process: impl Future<Output = ()>, signal: impl Future<Output = ()>) -> bool {
+	tokio::select! {
+		_ = signal => true,
+		_ = process => false,
+	}
+}
+
+#[cfg(unix)]
+fn shutdown_signal() -> Result<impl Future<Output = ()>> {
+	let mut sigint = signal(SignalKind::interrupt()).into_diagnostic()?;
+	let mut sigterm = signal(SignalKind::terminate()).into_diagnostic()?;
+
+	Ok(async move {
+		tokio::select! {
+			_ = sigint.recv() => event!(Level::INFO, "received SIGINT"),
+			_ = sigterm.recv() => event!(Level::INFO, "received SIGTERM"),
+		}
+	})
+}
+
+#[cfg(windows)]
+fn shutdown_signal() -> Result<impl Future<Output = ()>> {
+	let mut sig_c = ctrl_c().into_diagnostic()?;
+	let mut sig_break = ctrl_break().into_diagnostic()?;
+
+	Ok(async move {
+		tokio::select! {
+			_ = sig_c.recv() => event!(Level::INFO, "received CTRLC"),
+			_ = sig_break.recv() => event!(Level::INFO, "received CTRLBREAK"),
+		}
+	})
+}
+
 impl Deref for Context {
 	type Target = State;
 
@@ -94,6 +190,7 @@ pub struct State {
 	standby: Arc<Standby>,
 	config: Config,
 	database: Starchart<TomlBackend>,
+	runtime: Instant,
 }
 
 impl State {
@@ -123,8 +220,8 @@ impl State {
 	}
 
 	#[must_use]
-	pub const fn config(&self) -> Config {
-		self.config
+	pub const fn config(&self) -> &Config {
+		&self.config
 	}
 
 	#[must_use]
@@ -136,6 +233,107 @@ impl State {
 	pub fn interaction_client(&self) -> InteractionClient<'_> {
 		self.http.interaction(Config::application_id().unwrap())
 	}
+
+	#[must_use]
+	pub fn cache_stats(&self) -> CacheStats {
+		CacheStats::new(&self.cache)
+	}
+
+	// the bot runs a single shard, so this only ever has one entry.
+	#[must_use]
+	pub fn shard_statuses(&self) -> Vec<(u64, ShardStatus)> {
+		let (id, stage) = match self.shard.info() {
+			Ok(info) => (info.id(), Some(info.stage())),
+			Err(_) => (self.shard.config().shard()[0], None),
+		};
+
+		vec![(id, ShardStatus::from(stage))]
+	}
+
+	#[must_use]
+	pub fn uptime(&self) -> Duration {
+		self.runtime.elapsed()
+	}
+
+	#[must_use]
+	pub fn uptime_human(&self) -> String {
+		human_uptime(self.runtime)
+	}
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+	pub guilds: usize,
+	pub users: usize,
+	pub members: usize,
+	pub channels: usize,
+	pub roles: usize,
+	pub emojis: usize,
+}
+
+impl CacheStats {
+	#[must_use]
+	pub fn new(cache: &Cache) -> Self {
+		let stats = cache.stats();
+
+		Self {
+			guilds: stats.guilds(),
+			users: stats.users(),
+			members: stats.members(),
+			channels: stats.channels_guild() + stats.channels_private(),
+			roles: stats.roles(),
+			emojis: stats.emojis(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShardStatus {
+	Connected,
+	Connecting,
+	Resuming,
+	Disconnected,
+}
+
+// a shard without an active session hasn't connected yet, or has been shut down.
+impl From<Option<Stage>> for ShardStatus {
+	fn from(stage: Option<Stage>) -> Self {
+		match stage {
+			Some(Stage::Connected) => Self::Connected,
+			Some(Stage::Handshaking | Stage::Identifying) => Self::Connecting,
+			Some(Stage::Resuming) => Self::Resuming,
+			_ => Self::Disconnected,
+		}
+	}
+}
+
+impl Display for ShardStatus {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str(match self {
+			Self::Connected => "Connected",
+			Self::Connecting => "Connecting",
+			Self::Resuming => "Resuming",
+			Self::Disconnected => "Disconnected",
+		})
+	}
+}
+
+fn human_uptime(started: Instant) -> String {
+	human_duration(started.elapsed())
+}
+
+// formats as `2d 4h 13m`, leaving out leading units that are zero.
+fn human_duration(duration: Duration) -> String {
+	let minutes = duration.as_secs() / 60;
+	let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+
+	if days > 0 {
+		format!("{}d {}h {}m", days, hours, minutes)
+	} else if hours > 0 {
+		format!("{}h {}m", hours, minutes)
+	} else {
+		format!("{}m", minutes)
+	}
 }
 
 pub trait QuickAccess {
@@ -161,7 +359,7 @@ pub trait QuickAccess {
 		self.context().0.standby()
 	}
 
-	fn config(&self) -> Config {
+	fn config(&self) -> &Config {
 		self.context().0.config()
 	}
 
@@ -173,3 +371,147 @@ pub trait QuickAccess {
 		self.context().0.interaction_client()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use futures_util::future::pending;
+	use serde_json::json;
+	use tokio::sync::oneshot;
+	use twilight_cache_inmemory::InMemoryCache;
+	use twilight_gateway::shard::Stage;
+	use twilight_model::{gateway::payload::incoming::GuildCreate, guild::Guild};
+
+	use super::{human_duration, human_uptime, until_signal, CacheStats, ShardStatus};
+
+	#[test]
+	fn test_uptime() {
+		assert_eq!(human_uptime(Instant::now()), "0m");
+
+		let started = Instant::now()
+			.checked_sub(Duration::from_secs(3_660))
+			.expect("the clock has run for over an hour");
+
+		assert_eq!(human_uptime(started), "1h 1m");
+
+		assert_eq!(human_duration(Duration::from_secs(3_599)), "59m");
+		assert_eq!(
+			human_duration(Duration::from_secs(2 * 86_400 + 4 * 3_600 + 13 * 60 + 59)),
+			"2d 4h 13m"
+		);
+	}
+
+	#[test]
+	fn test_cache_stats() {
+		let cache = InMemoryCache::new();
+
+		assert_eq!(CacheStats::new(&cache), CacheStats::default());
+
+		let channel = |id: &str| {
+			json!({
+				"guild_id": "10",
+				"id": id,
+				"last_message_id": null,
+				"name": "general",
+				"nsfw": false,
+				"parent_id": null,
+				"permission_overwrites": [],
+				"position": 0,
+				"rate_limit_per_user": 0,
+				"topic": null,
+				"type": 0,
+			})
+		};
+		let role = |id: &str| {
+			json!({
+				"color": 0,
+				"hoist": false,
+				"id": id,
+				"managed": false,
+				"mentionable": false,
+				"name": "role",
+				"permissions": "0",
+				"position": 0,
+			})
+		};
+
+		let guild: Guild = serde_json::from_value(json!({
+			"afk_channel_id": null,
+			"afk_timeout": 300,
+			"application_id": null,
+			"banner": null,
+			"channels": [channel("11"), channel("12"), channel("13")],
+			"default_message_notifications": 0,
+			"description": null,
+			"discovery_splash": null,
+			"emojis": [],
+			"explicit_content_filter": 0,
+			"features": [],
+			"icon": null,
+			"id": "10",
+			"large": false,
+			"members": [],
+			"mfa_level": 0,
+			"name": "guild",
+			"nsfw_level": 0,
+			"owner_id": "1",
+			"preferred_locale": "en-US",
+			"premium_progress_bar_enabled": false,
+			"premium_tier": 0,
+			"presences": [],
+			"roles": [role("10"), role("14")],
+			"rules_channel_id": null,
+			"splash": null,
+			"stage_instances": [],
+			"stickers": [],
+			"system_channel_flags": 0,
+			"system_channel_id": null,
+			"threads": [],
+			"unavailable": false,
+			"vanity_url_code": null,
+			"verification_level": 0,
+			"voice_states": [],
+		}))
+		.unwrap();
+
+		cache.update(&GuildCreate(guild));
+
+		let stats = CacheStats::new(&cache);
+
+		assert_eq!(stats.guilds, 1);
+		assert_eq!(stats.channels, 3);
+		assert_eq!(stats.roles, 2);
+	}
+
+	#[test]
+	fn test_shard_status() {
+		assert_eq!(
+			ShardStatus::from(Some(Stage::Connected)),
+			ShardStatus::Connected
+		);
+		assert_eq!(
+			ShardStatus::from(Some(Stage::Identifying)),
+			ShardStatus::Connecting
+		);
+		assert_eq!(
+			ShardStatus::from(Some(Stage::Resuming)),
+			ShardStatus::Resuming
+		);
+		assert_eq!(ShardStatus::from(None), ShardStatus::Disconnected);
+		assert_eq!(ShardStatus::Resuming.to_string(), "Resuming");
+	}
+
+	#[tokio::test]
+	async fn test_signal_ends_loop() {
+		let (sender, receiver) = oneshot::channel::<()>();
+		let stopped = tokio::spawn(until_signal(pending(), async {
+			receiver.await.ok();
+		}));
+
+		sender.send(()).unwrap();
+
+		assert!(stopped.await.unwrap());
+		assert!(!until_signal(async {}, pending()).await);
+	}
+}
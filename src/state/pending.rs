@@ -0,0 +1,90 @@
+use std::{
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Duration,
+};
+
+use tokio::time::{sleep, timeout};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// counts the sections that must finish before the process exits, like database writes. only
+// wrap the write itself, a guard held for a whole handler (e.g. one waiting on button clicks)
+// would hold up every shutdown until the timeout.
+#[derive(Debug, Default)]
+pub struct PendingTasks(AtomicUsize);
+
+impl PendingTasks {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(AtomicUsize::new(0))
+	}
+
+	// never waits, so new work isn't held up while a shutdown is waiting.
+	pub fn track(&self) -> PendingGuard<'_> {
+		self.0.fetch_add(1, Ordering::SeqCst);
+
+		PendingGuard(self)
+	}
+
+	#[must_use]
+	pub fn count(&self) -> usize {
+		self.0.load(Ordering::SeqCst)
+	}
+
+	// returns whether every task finished before the timeout.
+	pub async fn wait(&self, limit: Duration) -> bool {
+		timeout(limit, async {
+			while self.count() > 0 {
+				sleep(POLL_INTERVAL).await;
+			}
+		})
+		.await
+		.is_ok()
+	}
+}
+
+#[derive(Debug)]
+#[must_use = "the task is only tracked while the guard is held"]
+pub struct PendingGuard<'a>(&'a PendingTasks);
+
+impl Drop for PendingGuard<'_> {
+	fn drop(&mut self) {
+		self.0 .0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use tokio::time::sleep;
+
+	use super::PendingTasks;
+
+	static PENDING: PendingTasks = PendingTasks::new();
+
+	#[tokio::test]
+	async fn test_waits_for_pending_task() {
+		let guard = PENDING.track();
+		let task = tokio::spawn(async move {
+			sleep(Duration::from_millis(20)).await;
+			drop(guard);
+		});
+
+		// new work is tracked right away, even while something is waiting.
+		let waiting = tokio::spawn(PENDING.wait(Duration::from_secs(5)));
+		drop(PENDING.track());
+
+		assert!(waiting.await.unwrap());
+		assert_eq!(PENDING.count(), 0);
+		task.await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_wait_timeout() {
+		let pending = PendingTasks::new();
+		let _guard = pending.track();
+
+		assert!(!pending.wait(Duration::from_millis(10)).await);
+	}
+}
@@ -0,0 +1,90 @@
+// counters exposed through the `metrics` facade, install a recorder (such as a prometheus
+// exporter) to collect them. without the `metrics` feature these compile to nothing.
+use std::time::Duration;
+
+#[cfg(feature = "metrics")]
+use metrics::{histogram, increment_counter};
+use twilight_gateway::Event;
+
+#[cfg(feature = "metrics")]
+pub fn event_processed(event: &Event) {
+	let kind = event.kind().name().unwrap_or("UNKNOWN");
+
+	increment_counter!("starlight_events_total", "kind" => kind);
+}
+
+#[cfg(not(feature = "metrics"))]
+#[allow(clippy::missing_const_for_fn)]
+pub fn event_processed(_: &Event) {}
+
+#[cfg(feature = "metrics")]
+pub fn command_invoked(name: &str, latency: Duration) {
+	increment_counter!("starlight_commands_total", "command" => name.to_owned());
+	histogram!("starlight_command_duration_seconds", latency, "command" => name.to_owned());
+}
+
+#[cfg(not(feature = "metrics"))]
+#[allow(clippy::missing_const_for_fn)]
+pub fn command_invoked(_: &str, _: Duration) {}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+	use std::sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	};
+
+	use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Recorder, Unit};
+	use twilight_gateway::Event;
+
+	// only counts increments of `starlight_events_total`, the other metrics are dropped.
+	struct EventRecorder(AtomicU64);
+
+	struct EventCounter(&'static AtomicU64);
+
+	impl CounterFn for EventCounter {
+		fn increment(&self, value: u64) {
+			self.0.fetch_add(value, Ordering::SeqCst);
+		}
+
+		fn absolute(&self, value: u64) {
+			self.0.fetch_max(value, Ordering::SeqCst);
+		}
+	}
+
+	impl Recorder for EventRecorder {
+		fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+		fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+		fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: &'static str) {}
+
+		fn register_counter(&self, key: &Key) -> Counter {
+			if key.name() == "starlight_events_total" {
+				Counter::from_arc(Arc::new(EventCounter(&RECORDER.0)))
+			} else {
+				Counter::noop()
+			}
+		}
+
+		fn register_gauge(&self, _: &Key) -> Gauge {
+			Gauge::noop()
+		}
+
+		fn register_histogram(&self, _: &Key) -> Histogram {
+			Histogram::noop()
+		}
+	}
+
+	static RECORDER: EventRecorder = EventRecorder(AtomicU64::new(0));
+
+	#[test]
+	fn test_event_counter() {
+		metrics::set_recorder(&RECORDER).unwrap();
+
+		super::event_processed(&Event::GatewayHeartbeatAck);
+		super::event_processed(&Event::GatewayHeartbeatAck);
+
+		assert_eq!(RECORDER.0.load(Ordering::SeqCst), 2);
+	}
+}
@@ -1,6 +1,9 @@
+use std::time::Duration;
 #[cfg(feature = "docker")]
 use std::{io::Error as IoError, net::ToSocketAddrs};
 
+use twilight_model::guild::Permissions;
+
 use crate::prelude::*;
 
 #[derive(Debug, Error)]
@@ -81,9 +84,14 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
 	result
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DefaultMessages {
 	PermissionDenied,
+	MissingPermissions(Permissions),
+	GuildOnly,
+	Blocked,
+	Unavailable,
+	Cooldown(Duration),
 }
 
 impl Display for DefaultMessages {
@@ -92,6 +100,72 @@ impl Display for DefaultMessages {
 			Self::PermissionDenied => {
 				f.write_str("you do not have permission to perform this action")
 			}
+			Self::MissingPermissions(permissions) => write!(
+				f,
+				"you need the following permissions to do this: {}",
+				permission_names(*permissions)
+			),
+			Self::GuildOnly => f.write_str("this command can only be used in a guild"),
+			Self::Blocked => f.write_str("you have been blocked from using commands in this guild"),
+			Self::Unavailable => f.write_str("this command isn't available right now"),
+			Self::Cooldown(remaining) => write!(
+				f,
+				"this command is on cooldown, try again in {} seconds",
+				remaining.as_secs() + 1
+			),
 		}
 	}
 }
+
+// `MANAGE_GUILD | BAN_MEMBERS` becomes `manage guild, ban members`.
+fn permission_names(permissions: Permissions) -> String {
+	format!("{:?}", permissions)
+		.split(" | ")
+		.map(|name| name.to_lowercase().replace('_', " "))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use twilight_model::guild::Permissions;
+
+	use super::DefaultMessages;
+
+	#[test]
+	fn test_render_messages() {
+		assert_eq!(
+			DefaultMessages::PermissionDenied.to_string(),
+			"you do not have permission to perform this action"
+		);
+		assert_eq!(
+			DefaultMessages::MissingPermissions(Permissions::MANAGE_GUILD).to_string(),
+			"you need the following permissions to do this: manage guild"
+		);
+		assert_eq!(
+			DefaultMessages::MissingPermissions(
+				Permissions::BAN_MEMBERS | Permissions::MANAGE_GUILD
+			)
+			.to_string(),
+			"you need the following permissions to do this: ban members, manage guild"
+		);
+		assert_eq!(
+			DefaultMessages::GuildOnly.to_string(),
+			"this command can only be used in a guild"
+		);
+		assert_eq!(
+			DefaultMessages::Blocked.to_string(),
+			"you have been blocked from using commands in this guild"
+		);
+		assert_eq!(
+			DefaultMessages::Unavailable.to_string(),
+			"this command isn't available right now"
+		);
+		assert_eq!(
+			DefaultMessages::Cooldown(Duration::from_millis(2500)).to_string(),
+			"this command is on cooldown, try again in 3 seconds"
+		);
+	}
+}
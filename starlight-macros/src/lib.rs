@@ -5,6 +5,7 @@ pub use model::{ModelError, ModelInput};
 
 mod private {
 	use twilight_model::{
+		application::command::Command,
 		channel::{Channel, Message},
 		guild::{Emoji, Member, Role},
 		user::{CurrentUser, User},
@@ -19,6 +20,7 @@ mod private {
 	impl Sealed for User {}
 	impl Sealed for Channel {}
 	impl Sealed for Member {}
+	impl Sealed for Command {}
 
 	impl<T: Sealed> Sealed for Option<T> {}
 	impl<T: Sealed> Sealed for Vec<T> {}
@@ -38,6 +40,26 @@ macro_rules! debug_unreachable {
 	};
 }
 
+// the recoverable counterpart of `debug_unreachable`. it still panics in debug builds, but release
+// builds log the message and get an error back instead of undefined behavior.
+#[macro_export]
+macro_rules! unexpected {
+	() => {
+		$crate::unexpected!("entered unexpected code")
+	};
+	($($arg:tt)*) => {{
+		let message = format!($($arg)*);
+
+		if cfg!(debug_assertions) {
+			panic!("{}", message);
+		}
+
+		::tracing::event!(::tracing::Level::ERROR, %message, "entered unexpected code");
+
+		::miette::miette!("{}", message)
+	}};
+}
+
 #[macro_export]
 macro_rules! status {
 	($request:expr) => {
@@ -112,3 +134,33 @@ macro_rules! cloned {
 		}
 	);
 }
+
+#[cfg(test)]
+mod tests {
+	fn checked(value: Option<u8>) -> miette::Result<u8> {
+		match value {
+			Some(value) => Ok(value),
+			None => Err(unexpected!("missing value")),
+		}
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "missing value")]
+	fn test_unexpected_panics_in_debug() {
+		let _ = checked(None);
+	}
+
+	#[test]
+	#[cfg(not(debug_assertions))]
+	fn test_unexpected_errors_in_release() {
+		let error = checked(None).unwrap_err();
+
+		assert_eq!(error.to_string(), "missing value");
+	}
+
+	#[test]
+	fn test_unexpected_expected_path() {
+		assert_eq!(checked(Some(1)).ok(), Some(1));
+	}
+}
@@ -5,6 +5,7 @@ use twilight_http::{
 	Error as HttpError,
 };
 use twilight_model::{
+	application::command::Command,
 	channel::{Channel, Message},
 	guild::{Emoji, Member, Role},
 	user::{CurrentUser, User},
@@ -59,6 +60,7 @@ impl ModelInput for Emoji {}
 impl ModelInput for User {}
 impl ModelInput for Channel {}
 impl ModelInput for Member {}
+impl ModelInput for Command {}
 
 impl<T: ModelInput> ModelInput for Option<T> {}
 impl<T: ModelInput> ModelInput for Vec<T> {}